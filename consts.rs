@@ -45,6 +45,11 @@ pub const MAX_LINEAR: i64 = 1_123_200; // 13 days
 pub const INSTANT_TOKEN_PERCENTAGE_NUM: u64 = 10;
 pub const INSTANT_TOKEN_PERCENTAGE_DENOM: u64 = 100;
 
+/// Rent paid to a property's stakers on top of a stake purchase, as a share of
+/// the purchase's quote fee
+pub const PROPERTY_RENT_FEE_PERCENTAGE_NUM: u64 = 50;
+pub const PROPERTY_RENT_FEE_PERCENTAGE_DENOM: u64 = 100;
+
 pub const POINTS_PDA: &'static [u8; 10] = b"points_pda";
 
 #[cfg(feature = "testing")]
@@ -0,0 +1,43 @@
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+#[derive(Accounts)]
+pub struct CheckPoolHealth<'info> {
+    pub pool: Account<'info, BoundPool>,
+    #[account(constraint = pool.quote_reserve.vault == quote_vault.key())]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(constraint = pool.meme_reserve.vault == meme_vault.key())]
+    pub meme_vault: InterfaceAccount<'info, TokenAccount>,
+}
+
+/// Read-only check that the quote vault backs the pool's reserve and admin
+/// fees, and the meme vault the meme reserve, admin fees, tickets and LP
+/// tokens, failing with `InvariantViolation` on any deficit.
+pub fn handle(ctx: Context<CheckPoolHealth>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let surplus = accs.pool.quote_vault_surplus(accs.quote_vault.amount)?;
+    let meme_surplus = accs.pool.meme_vault_surplus(accs.meme_vault.amount)?;
+
+    msg!(
+        "quote_vault: {}\n quote_reserve: {}\n admin_fees_quote: {}\n quote_surplus: {}",
+        accs.quote_vault.amount,
+        accs.pool.quote_reserve.tokens,
+        accs.pool.admin_fees_quote,
+        surplus
+    );
+
+    msg!(
+        "meme_vault: {}\n meme_reserve: {}\n admin_fees_meme: {}\n ticket_tokens: {}\n lp_tokens: {}\n airdropped_tokens: {}\n meme_surplus: {}",
+        accs.meme_vault.amount,
+        accs.pool.meme_reserve.tokens,
+        accs.pool.admin_fees_meme,
+        accs.pool.ticket_tokens,
+        accs.pool.config.omega_m,
+        accs.pool.airdropped_tokens,
+        meme_surplus
+    );
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::airdrop::{AirdropDistributor, ClaimStatus};
+use crate::models::bound::BoundPool;
+use crate::models::staked_lp::MemeTicket;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(index: u64, amount: u64, ticket_number: u64)]
+pub struct ClaimAirdrop<'info> {
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    #[account(mut)]
+    pub pool: Account<'info, BoundPool>,
+    #[account(
+        mut,
+        has_one = pool,
+        seeds = [AirdropDistributor::AIRDROP_PREFIX, pool.key().as_ref()],
+        bump
+    )]
+    pub distributor: Account<'info, AirdropDistributor>,
+    #[account(
+        init,
+        payer = claimant,
+        space = ClaimStatus::space(),
+        seeds = [
+            ClaimStatus::CLAIM_STATUS_PREFIX,
+            distributor.key().as_ref(),
+            index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub claim_status: Account<'info, ClaimStatus>,
+    #[account(
+        init,
+        payer = claimant,
+        space = MemeTicket::space(),
+        seeds = [pool.key().as_ref(), claimant.key().as_ref(), ticket_number.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub meme_ticket: Account<'info, MemeTicket>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Claims an airdrop allocation into a vesting meme ticket.
+///
+/// The tokens stay in the pool's meme vault. The ticket is locked for the
+/// pool's vesting period and can't be sold back into the curve, it's only
+/// paid out through vesting once the pool migrated. The claim status PDA of
+/// the leaf index makes every leaf claimable once.
+///
+/// Liquid pools don't issue tickets, so their airdrops can't be claimed.
+///
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `index` - Index of the claimant's leaf
+/// * `amount` - Amount of meme tokens of the leaf
+/// * `_ticket_number` - Ticket number for the meme ticket PDA
+/// * `proof` - Merkle proof of the leaf
+pub fn handle(
+    ctx: Context<ClaimAirdrop>,
+    index: u64,
+    amount: u64,
+    _ticket_number: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let accs = ctx.accounts;
+    let now = Clock::get()?.unix_timestamp;
+
+    if accs.pool.liquid {
+        return Err(error!(err::acc("Liquid pools don't issue airdrop tickets")));
+    }
+
+    if accs.distributor.clawed_back || accs.distributor.is_expired(now) {
+        return Err(error!(AmmError::AirdropExpired));
+    }
+
+    let leaf = AirdropDistributor::leaf(index, &accs.claimant.key(), amount);
+    if !accs.distributor.verify(&proof, leaf) {
+        return Err(error!(AmmError::InvalidAirdropProof));
+    }
+
+    if amount > accs.distributor.unclaimed_amount() || amount > accs.pool.airdropped_tokens {
+        return Err(error!(AmmError::AirdroppedTokensOvercap));
+    }
+
+    let distributor = &mut accs.distributor;
+    distributor.claimed_amount += amount;
+    distributor.num_claimed += 1;
+
+    // The claimed tokens now back the ticket instead of the airdrop reserve
+    accs.pool.airdropped_tokens -= amount;
+
+    let claim_status = &mut accs.claim_status;
+    claim_status.distributor = distributor.key();
+    claim_status.claimant = accs.claimant.key();
+    claim_status.amount = amount;
+    claim_status.claimed_at = now;
+
+    accs.meme_ticket.setup_airdrop(
+        accs.pool.key(),
+        accs.claimant.key(),
+        amount,
+        accs.pool.vesting_period,
+    )?;
+
+    msg!("airdrop_index: {}\n airdrop_claimed: {}", index, amount);
+
+    Ok(())
+}
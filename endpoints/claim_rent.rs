@@ -0,0 +1,70 @@
+use crate::models::board::{Board, PropertyStake, PropertyState};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Accounts required to claim the rent accrued by a property stake.
+#[derive(Accounts)]
+pub struct ClaimRent<'info> {
+    pub board: Account<'info, Board>,
+    #[account(
+        seeds = [
+            PropertyState::PROPERTY_STATE_PREFIX,
+            board.key().as_ref(),
+            property_stake.property_position.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub property_state: Account<'info, PropertyState>,
+    #[account(mut, has_one = board, has_one = owner)]
+    pub property_stake: Account<'info, PropertyStake>,
+    #[account(
+        mut,
+        constraint = board.rent_vault == rent_vault.key()
+    )]
+    pub rent_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user_sol: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    /// CHECK: board pda signer
+    #[account(seeds = [Board::SIGNER_PDA_PREFIX, board.key().as_ref()], bump)]
+    pub board_signer: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimRent<'info> {
+    fn send_rent_to_user(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.rent_vault.to_account_info(),
+            to: self.user_sol.to_account_info(),
+            authority: self.board_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Pays out the rent accrued by a property stake since its last claim.
+pub fn handle(ctx: Context<ClaimRent>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let rent = accs.property_stake.pending_rent(&accs.property_state)?;
+
+    accs.property_stake.checkpoint_rent(&accs.property_state)?;
+
+    if rent > 0 {
+        let seeds = &[
+            Board::SIGNER_PDA_PREFIX,
+            &accs.board.key().to_bytes()[..],
+            &[ctx.bumps.board_signer],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        token::transfer(accs.send_rent_to_user().with_signer(signer_seeds), rent)?;
+    }
+
+    msg!("rent_claimed: {}", rent);
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::airdrop::AirdropDistributor;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClawbackAirdrop<'info> {
+    pub sender: Signer<'info>,
+    #[account(
+        mut,
+        constraint = pool.creator_addr == sender.key()
+            @ err::acc("Only the pool creator can claw back the airdrop"),
+    )]
+    pub pool: Account<'info, BoundPool>,
+    #[account(
+        mut,
+        has_one = pool,
+        seeds = [AirdropDistributor::AIRDROP_PREFIX, pool.key().as_ref()],
+        bump
+    )]
+    pub distributor: Account<'info, AirdropDistributor>,
+}
+
+/// Releases the airdrop tokens nobody claimed before the expiry.
+///
+/// The tokens never leave the pool's meme vault, they stop being reserved for
+/// the airdrop and go back to the pool's LP tokens.
+pub fn handle(ctx: Context<ClawbackAirdrop>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    if !accs.distributor.is_expired(Clock::get()?.unix_timestamp) {
+        return Err(error!(AmmError::AirdropNotExpired));
+    }
+
+    if accs.distributor.clawed_back {
+        return Err(error!(err::acc("Airdrop was already clawed back")));
+    }
+
+    let unclaimed = accs.distributor.unclaimed_amount();
+
+    accs.distributor.clawed_back = true;
+    accs.pool.airdropped_tokens = accs.pool.airdropped_tokens.saturating_sub(unclaimed);
+
+    msg!("airdrop_clawed_back: {}", unclaimed);
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use crate::err;
+use crate::models::board::{Board, PlayerState};
+use crate::models::bound::BoundPool;
+use crate::models::dice::CommitReveal;
+use crate::MonopolyError;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Accounts required to commit to the next dice roll of a player.
+///
+/// The pool, fee vault, quote account and token program are only needed to
+/// forfeit an expired commitment.
+#[derive(Accounts)]
+pub struct CommitRoll<'info> {
+    pub board: Account<'info, Board>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + PlayerState::INIT_SPACE,
+        seeds = [PlayerState::PLAYER_STATE_PREFIX, board.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub player_state: Account<'info, PlayerState>,
+    #[account(constraint = board.pool == pool.key())]
+    pub pool: Option<Account<'info, BoundPool>>,
+    #[account(mut)]
+    pub fee_quote_vault: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub user_sol: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CommitRoll<'info> {
+    /// Charges the board's highest landing fee into the pool's fee vault.
+    fn pay_forfeit(&self) -> Result<u64> {
+        let (pool, fee_quote_vault, user_sol, token_program) = match (
+            &self.pool,
+            &self.fee_quote_vault,
+            &self.user_sol,
+            &self.token_program,
+        ) {
+            (Some(pool), Some(fee_quote_vault), Some(user_sol), Some(token_program)) => {
+                (pool, fee_quote_vault, user_sol, token_program)
+            }
+            _ => {
+                return Err(error!(err::acc(
+                    "Pool, fee vault, quote account and token program are required to forfeit a roll"
+                )))
+            }
+        };
+
+        if pool.fee_vault_quote != fee_quote_vault.key() {
+            return Err(error!(err::acc("Fee vault must be the pool's quote fee vault")));
+        }
+
+        let amount = Board::dollars_to_quote(
+            self.board.max_landing_fee()?,
+            pool.config.decimals.quote,
+        )?;
+
+        let cpi_accounts = Transfer {
+            from: user_sol.to_account_info(),
+            to: fee_quote_vault.to_account_info(),
+            authority: self.owner.to_account_info(),
+        };
+
+        token::transfer(
+            CpiContext::new(token_program.to_account_info(), cpi_accounts),
+            amount,
+        )?;
+
+        Ok(amount)
+    }
+}
+
+/// Commits to the secret of the next roll, joining the board on first use.
+///
+/// A previous commitment that wasn't revealed within `REVEAL_WINDOW_SLOTS` is
+/// forfeited first. Its roll could be known before the deadline, so the player
+/// pays the board's highest rent or tax into the pool's fee vault and goes to
+/// jail, which costs at least as much as any roll they could have withheld.
+///
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `commitment` - Hash of the secret revealed by `roll_dice`
+pub fn handle(ctx: Context<CommitRoll>, commitment: [u8; 32]) -> Result<()> {
+    let accs = ctx.accounts;
+
+    if accs.player_state.board == Pubkey::default() {
+        accs.player_state.board = accs.board.key();
+        accs.player_state.owner = accs.owner.key();
+    }
+
+    let slot = Clock::get()?.slot;
+
+    if accs.player_state.has_commitment() {
+        if !CommitReveal::is_expired(accs.player_state.commit_slot, slot) {
+            return Err(error!(MonopolyError::RollAlreadyCommitted));
+        }
+
+        let paid = accs.pay_forfeit()?;
+        accs.player_state.forfeit_roll();
+
+        msg!("roll_forfeited: {}", paid);
+    }
+
+    let player = &mut accs.player_state;
+
+    if player.pending_landing {
+        return Err(error!(MonopolyError::LandingNotResolved));
+    }
+
+    player.commitment = commitment;
+    player.commit_slot = slot;
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
+use anchor_spl::metadata::{
+    create_metadata_accounts_v3, CreateMetadataAccountsV3, Metadata as MetadataProgram,
+};
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
+use anchor_spl::token_interface::{self, Mint, SetAuthority, TokenInterface};
+
+/// Accounts required to create the Metaplex metadata of a pool's meme mint.
+///
+/// The pool signer PDA is still the mint authority at this point, it signs the
+/// metadata creation and then gives the authority up for good.
+#[derive(Accounts)]
+pub struct CreateMetadata<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    #[account(
+        mut,
+        constraint = pool.creator_addr == sender.key()
+            @ err::acc("Only the pool creator can create its metadata"),
+        constraint = pool.meme_reserve.mint == meme_mint.key()
+            @ err::acc("Meme mint must be the pool's meme mint"),
+        constraint = !pool.supply_fixed @ AmmError::MemeSupplyAlreadyFixed,
+    )]
+    pub pool: Account<'info, BoundPool>,
+    #[account(mut)]
+    pub meme_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: metadata pda, initialized by the metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), meme_mint.key().as_ref()],
+        seeds::program = metadata_program.key(),
+        bump
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: pool_pda
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    pub pool_signer: AccountInfo<'info>,
+    pub metadata_program: Program<'info, MetadataProgram>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> CreateMetadata<'info> {
+    fn create_metadata_ctx(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, CreateMetadataAccountsV3<'info>> {
+        let cpi_accounts = CreateMetadataAccountsV3 {
+            metadata: self.metadata.to_account_info(),
+            mint: self.meme_mint.to_account_info(),
+            mint_authority: self.pool_signer.to_account_info(),
+            payer: self.sender.to_account_info(),
+            update_authority: self.pool_signer.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            rent: self.rent.to_account_info(),
+        };
+
+        let cpi_program = self.metadata_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    fn revoke_mint_authority_ctx(&self) -> CpiContext<'_, '_, '_, 'info, SetAuthority<'info>> {
+        let cpi_accounts = SetAuthority {
+            current_authority: self.pool_signer.to_account_info(),
+            account_or_mint: self.meme_mint.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Creates the metadata of the meme mint and revokes its mint authority.
+///
+/// Once this ran, the meme supply is provably fixed at what `new_pool` minted
+/// and the metadata can't be changed anymore.
+///
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `name` - Token name
+/// * `symbol` - Token symbol
+/// * `uri` - URI of the off-chain metadata JSON
+pub fn handle(ctx: Context<CreateMetadata>, name: String, symbol: String, uri: String) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &accs.pool.key().to_bytes()[..],
+        &[ctx.bumps.pool_signer],
+    ];
+
+    let signer_seeds = &[&seeds[..]];
+
+    create_metadata_accounts_v3(
+        accs.create_metadata_ctx().with_signer(signer_seeds),
+        DataV2 {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        false,
+        true,
+        None,
+    )?;
+
+    token_interface::set_authority(
+        accs.revoke_mint_authority_ctx().with_signer(signer_seeds),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    accs.pool.supply_fixed = true;
+
+    Ok(())
+}
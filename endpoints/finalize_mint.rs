@@ -0,0 +1,66 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
+use anchor_spl::token_interface::{self, Mint, SetAuthority, TokenInterface};
+
+/// Accounts required to revoke the mint authority of a pool's meme mint
+/// without creating its metadata.
+#[derive(Accounts)]
+pub struct FinalizeMint<'info> {
+    pub sender: Signer<'info>,
+    #[account(
+        mut,
+        constraint = pool.creator_addr == sender.key()
+            @ err::acc("Only the pool creator can finalize its mint"),
+        constraint = pool.meme_reserve.mint == meme_mint.key()
+            @ err::acc("Meme mint must be the pool's meme mint"),
+        constraint = !pool.supply_fixed @ AmmError::MemeSupplyAlreadyFixed,
+    )]
+    pub pool: Account<'info, BoundPool>,
+    #[account(mut)]
+    pub meme_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: pool_pda
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    pub pool_signer: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> FinalizeMint<'info> {
+    fn revoke_mint_authority_ctx(&self) -> CpiContext<'_, '_, '_, 'info, SetAuthority<'info>> {
+        let cpi_accounts = SetAuthority {
+            current_authority: self.pool_signer.to_account_info(),
+            account_or_mint: self.meme_mint.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Revokes the mint authority of the meme mint and marks the supply as fixed.
+///
+/// For pools that skip `create_metadata`, which does the same after creating
+/// the metadata.
+pub fn handle(ctx: Context<FinalizeMint>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &accs.pool.key().to_bytes()[..],
+        &[ctx.bumps.pool_signer],
+    ];
+
+    let signer_seeds = &[&seeds[..]];
+
+    token_interface::set_authority(
+        accs.revoke_mint_authority_ctx().with_signer(signer_seeds),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    accs.pool.supply_fixed = true;
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct GetPoolState<'info> {
+    pub pool: Account<'info, BoundPool>,
+}
+
+/// Snapshot of a pool's bonding progress, returned by [`handle`]
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PoolState {
+    /// Meme tokens left on the curve
+    pub meme_reserve: u64,
+    /// Quote tokens raised by the curve
+    pub quote_reserve: u64,
+    /// Price of one whole meme token in quote tokens, scaled by
+    /// [`crate::models::bound::PRICE_PRECISION`]
+    pub spot_price: u128,
+    /// Value of the whole meme supply at the spot price, in quote tokens
+    pub market_cap: u64,
+    /// Progress of the quote reserve toward `gamma_s`, in basis points
+    pub progress_bps: u64,
+    /// Quote tokens still to be raised before the curve is complete
+    pub remaining_to_graduation: u64,
+    /// Whether the curve is complete and the pool no longer trades
+    pub locked: bool,
+}
+
+/// Read-only view of the pool's price and bonding progress, for frontends to
+/// simulate and read through the instruction's return data.
+pub fn handle(ctx: Context<GetPoolState>) -> Result<PoolState> {
+    let pool = &ctx.accounts.pool;
+
+    let state = PoolState {
+        meme_reserve: pool.meme_reserve.tokens,
+        quote_reserve: pool.quote_reserve.tokens,
+        spot_price: pool.spot_price()?,
+        market_cap: pool.market_cap()?,
+        progress_bps: pool.progress_bps()?,
+        remaining_to_graduation: pool.remaining_to_graduation(),
+        locked: pool.locked,
+    };
+
+    msg!(
+        "spot_price: {}\n market_cap: {}\n progress_bps: {}\n remaining_to_graduation: {}",
+        state.spot_price,
+        state.market_cap,
+        state.progress_bps,
+        state.remaining_to_graduation
+    );
+
+    Ok(state)
+}
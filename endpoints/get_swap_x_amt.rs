@@ -7,7 +7,7 @@
 
 use crate::models::bound::BoundPool;
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token_interface::TokenAccount;
 
 /// Account validation struct for getting swap amounts
 #[derive(Accounts)]
@@ -17,7 +17,7 @@ pub struct GetSwapXAmt<'info> {
     
     /// The quote token vault, must match the pool's quote reserve vault
     #[account(constraint = pool.quote_reserve.vault == quote_vault.key())]
-    pub quote_vault: Account<'info, TokenAccount>,
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
 }
 
 /// Calculates and logs the expected swap amounts for a given input amount
@@ -30,10 +30,11 @@ pub struct GetSwapXAmt<'info> {
 /// # Returns
 /// * `Result<()>` - Returns Ok if calculation succeeds
 pub fn handle(ctx: Context<GetSwapXAmt>, coin_in_amount: u64, coin_y_min_value: u64) -> Result<()> {
-    let swap_amount = ctx
-        .accounts
-        .pool
-        .swap_amounts(coin_in_amount, coin_y_min_value, false);
+    let pool = &ctx.accounts.pool;
+
+    let fees = pool.sell_fees(&pool.fees, Clock::get()?.slot)?;
+    let swap_amount =
+        pool.swap_amounts_with_fees(coin_in_amount, coin_y_min_value, false, &fees)?;
 
     msg!(
         "swapped_in: {}\n swapped_out: {}",
@@ -0,0 +1,61 @@
+use crate::err;
+use crate::models::board::{Board, CLASSIC_TILES};
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_interface::TokenAccount;
+
+/// Accounts required to create a game board on top of a bonding curve pool.
+///
+/// The board endpoints move quote tokens with the SPL Token program only, so
+/// pools quoted in a Token-2022 mint, whose transfer fees they don't account
+/// for, can't get a board.
+#[derive(Accounts)]
+pub struct InitBoard<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    #[account(
+        constraint = pool.creator_addr == sender.key()
+            @ err::acc("Only the pool creator can create its board")
+    )]
+    pub pool: Account<'info, BoundPool>,
+    #[account(
+        init,
+        payer = sender,
+        space = 8 + Board::INIT_SPACE,
+        seeds = [Board::BOARD_PREFIX, pool.key().as_ref()],
+        bump
+    )]
+    pub board: Account<'info, Board>,
+    #[account(
+        constraint = rent_vault.mint == pool.quote_reserve.mint
+            @ err::acc("Rent vault must be of quote mint"),
+        constraint = *rent_vault.to_account_info().owner == spl_token::ID
+            @ err::acc("Boards only support quote mints of the SPL Token program"),
+        constraint = rent_vault.owner == board_signer.key()
+            @ err::acc("Rent vault authority must match the board signer"),
+        constraint = rent_vault.close_authority == COption::None
+            @ err::acc("Rent vault must not have close authority"),
+        constraint = rent_vault.delegate == COption::None
+            @ err::acc("Rent vault must not have delegate"),
+    )]
+    pub rent_vault: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: board pda signer
+    #[account(seeds = [Board::SIGNER_PDA_PREFIX, board.key().as_ref()], bump)]
+    pub board_signer: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a board with the classic 40 tile layout for the given pool.
+pub fn handle(ctx: Context<InitBoard>) -> Result<()> {
+    let accs = ctx.accounts;
+    let board = &mut accs.board;
+
+    board.pool = accs.pool.key();
+    board.creator = accs.sender.key();
+    board.rent_vault = accs.rent_vault.key();
+    board.tiles = CLASSIC_TILES;
+
+    Ok(())
+}
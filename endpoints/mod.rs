@@ -2,15 +2,30 @@ pub use add_fees::*;
 pub use admin::*;
 pub use bound_merge_tickets::*;
 pub use chanswap::*;
+pub use check_pool_health::*;
+pub use claim_airdrop::*;
+pub use claim_rent::*;
+pub use clawback_airdrop::*;
 pub use close_ticket::*;
+pub use commit_roll::*;
 pub use create_metadata::*;
+pub use finalize_mint::*;
+pub use get_pool_state::*;
 pub use get_swap_x_amt::*;
 pub use get_swap_y_amt::*;
+pub use init_board::*;
 pub use init_chan_amm_pool::*;
 pub use init_quote_amm_pool::*;
 pub use init_staking_pool::*;
 pub use new_pool::*;
+pub use resolve_landing::*;
+pub use roll_dice::*;
+pub use sell_property_stake::*;
 pub use send_airdrop_funds::*;
+pub use set_airdrop_root::*;
+pub use set_dev_buy_cap::*;
+pub use set_fee_discount::*;
+pub use skim_pool::*;
 pub use staking_merge_tickets::*;
 pub use swap_x::*;
 pub use swap_y::*;
@@ -21,15 +36,30 @@ pub mod add_fees;
 pub mod admin;
 pub mod bound_merge_tickets;
 pub mod chanswap;
+pub mod check_pool_health;
+pub mod claim_airdrop;
+pub mod claim_rent;
+pub mod clawback_airdrop;
 pub mod close_ticket;
+pub mod commit_roll;
 pub mod create_metadata;
+pub mod finalize_mint;
+pub mod get_pool_state;
 pub mod get_swap_x_amt;
 pub mod get_swap_y_amt;
+pub mod init_board;
 pub mod init_chan_amm_pool;
 pub mod init_quote_amm_pool;
 pub mod init_staking_pool;
 pub mod new_pool;
+pub mod resolve_landing;
+pub mod roll_dice;
+pub mod sell_property_stake;
 pub mod send_airdrop_funds;
+pub mod set_airdrop_root;
+pub mod set_dev_buy_cap;
+pub mod set_fee_discount;
+pub mod skim_pool;
 pub mod staking_merge_tickets;
 pub mod swap_x;
 pub mod swap_y;
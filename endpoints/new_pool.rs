@@ -1,22 +1,34 @@
 use crate::consts::{
-    BP_FEE_KEY, DEFAULT_MAX_M, DEFAULT_MAX_M_LP, DEFAULT_PRICE_FACTOR_DENOMINATOR,
-    DEFAULT_PRICE_FACTOR_NUMERATOR, MAX_AIRDROPPED_TOKENS, MAX_LINEAR, MAX_MEME_TOKENS, MIN_LINEAR,
+    BP_FEE_KEY, MAX_AIRDROPPED_TOKENS, MAX_LINEAR, MIN_LINEAR,
 };
 use crate::err;
 use crate::err::AmmError;
-use crate::models::bound::{compute_alpha_abs, compute_beta, BoundPool, Config, Decimals};
+use crate::models::bound::{
+    check_supply_split, compute_alpha_abs, compute_beta, BoundPool, Config, Decimals, PriceLimit,
+    BPS_DENOM,
+};
+use crate::models::dev_buy_config::DevBuyConfig;
 use crate::models::fees::FEE;
 use crate::models::fees::{Fees, MEME_FEE};
+use crate::models::staked_lp::MemeTicket;
 use crate::models::target_config::TargetConfig;
 use crate::models::Reserve;
+use crate::libraries::MulDiv;
+use crate::token_ext;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program_option::COption;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{
+    self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+};
 /// Represents the accounts required for creating a new pool.
 ///
 /// This struct defines the accounts needed for the `new_pool` instruction. It includes the sender's account,
 /// the pool account, meme mint and vault accounts, quote mint and vault accounts, fee quote vault account,
 /// target configuration account, pool signer account, and the system and token programs.
+/// Mints and vaults can belong to either the SPL Token or the Token-2022 program.
+/// The creator's quote account, meme ticket and the quote token program are only needed
+/// for an initial dev-buy, a liquid pool's dev-buy takes the creator's meme account instead
+/// of the ticket.
 ///
 /// The `NewPool` struct is used to validate and manage the creation of a new pool within the AMM system.
 /// It ensures that all necessary accounts are present and meet the required conditions for pool creation.
@@ -40,10 +52,13 @@ pub struct NewPool<'info> {
             @ err::acc("Meme mint authority must be the pool signer"),
         constraint = meme_mint.freeze_authority == COption::None
             @ err::acc("Meme mint mustn't have a freeze authority"),
+        constraint = *meme_mint.to_account_info().owner == token_program.key()
+            @ err::acc("Meme mint must be owned by the given token program"),
     )]
     /// The account representing the meme mint.
-    pub meme_mint: Account<'info, Mint>,
+    pub meme_mint: InterfaceAccount<'info, Mint>,
     #[account(
+        mut,
         constraint = quote_vault.mint == quote_mint.key()
             @ err::acc("Quote vault must be of ticket mint"),
         constraint = quote_vault.owner == pool_signer.key()
@@ -54,9 +69,9 @@ pub struct NewPool<'info> {
             @ err::acc("Quote vault must not have delegate"),
     )]
     /// The account representing the quote vault.
-    pub quote_vault: Account<'info, TokenAccount>,
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
     /// The account representing the quote mint.
-    pub quote_mint: Account<'info, Mint>,
+    pub quote_mint: InterfaceAccount<'info, Mint>,
     #[account(
         constraint = fee_quote_vault.mint == quote_mint.key()
             @ err::acc("Fee quote vault must be of quote mint"),
@@ -68,7 +83,7 @@ pub struct NewPool<'info> {
             @ err::acc("Fee quote vault must not have delegate"),
     )]
     /// The account representing the fee quote vault.
-    pub fee_quote_vault: Account<'info, TokenAccount>,
+    pub fee_quote_vault: InterfaceAccount<'info, TokenAccount>,
     #[account(
         mut,
         constraint = meme_vault.mint == meme_mint.key()
@@ -81,20 +96,48 @@ pub struct NewPool<'info> {
             @ err::acc("Meme vault must not have delegate"),
     )]
     /// The account representing the meme vault.
-    pub meme_vault: Account<'info, TokenAccount>,
+    pub meme_vault: InterfaceAccount<'info, TokenAccount>,
     #[account(
         constraint = target_config.token_mint == quote_mint.key()
     )]
     /// The account representing the target configuration.
     pub target_config: Account<'info, TargetConfig>,
+    #[account(seeds = [DevBuyConfig::DEV_BUY_PREFIX, quote_mint.key().as_ref()], bump)]
+    /// The admin set dev-buy cap of the quote mint, dev-buys aren't allowed without it.
+    pub dev_buy_config: Option<Account<'info, DevBuyConfig>>,
     /// CHECK: pool_pda
     #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
     /// The account representing the pool signer.
     pub pool_signer: AccountInfo<'info>,
     /// The system program account.
     pub system_program: Program<'info, System>,
-    /// The token program account.
-    pub token_program: Program<'info, Token>,
+    /// The token program of the meme mint.
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        mut,
+        constraint = creator_quote.mint == quote_mint.key()
+            @ err::acc("Creator quote account must be of quote mint"),
+    )]
+    /// The creator's quote token account paying for the dev-buy.
+    pub creator_quote: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = sender,
+        space = MemeTicket::space(),
+        seeds = [MemeTicket::CREATOR_TICKET_PREFIX, pool.key().as_ref(), sender.key().as_ref()],
+        bump,
+    )]
+    /// The creator's meme ticket holding the dev-buy, apart from the tickets of their swaps.
+    pub creator_ticket: Option<Account<'info, MemeTicket>>,
+    #[account(
+        mut,
+        constraint = creator_meme.mint == meme_mint.key()
+            @ err::acc("Creator meme account must be of meme mint"),
+    )]
+    /// The creator's meme token account receiving the dev-buy of a liquid pool.
+    pub creator_meme: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The token program of the quote mint.
+    pub quote_token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 impl<'info> NewPool<'info> {
@@ -106,8 +149,8 @@ impl<'info> NewPool<'info> {
     /// # Returns
     ///
     /// A CPI context for minting meme tokens to the meme vault.
-    fn mint_meme_tokens(&self) -> CpiContext<'_, '_, '_, 'info, token::MintTo<'info>> {
-        let cpi_accounts = token::MintTo {
+    fn mint_meme_tokens(&self) -> CpiContext<'_, '_, '_, 'info, MintTo<'info>> {
+        let cpi_accounts = MintTo {
             mint: self.meme_mint.to_account_info(),
             to: self.meme_vault.to_account_info(),
             authority: self.pool_signer.to_account_info(),
@@ -116,6 +159,39 @@ impl<'info> NewPool<'info> {
         let cpi_program = self.token_program.to_account_info();
         CpiContext::new(cpi_program, cpi_accounts)
     }
+
+    /// Creates a CPI context for transferring the creator's dev-buy quote to the quote vault.
+    fn send_creator_tokens(
+        &self,
+        creator_quote: &InterfaceAccount<'info, TokenAccount>,
+        quote_token_program: &Interface<'info, TokenInterface>,
+    ) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: creator_quote.to_account_info(),
+            mint: self.quote_mint.to_account_info(),
+            to: self.quote_vault.to_account_info(),
+            authority: self.sender.to_account_info(),
+        };
+
+        let cpi_program = quote_token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Creates a CPI context for transferring the dev-buy of a liquid pool to the creator.
+    fn send_meme_to_creator(
+        &self,
+        creator_meme: &InterfaceAccount<'info, TokenAccount>,
+    ) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.meme_vault.to_account_info(),
+            mint: self.meme_mint.to_account_info(),
+            to: creator_meme.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
 }
 
 /// Handles the creation of a new pool.
@@ -131,6 +207,10 @@ impl<'info> NewPool<'info> {
 /// * `ctx`: The context of the current instruction.
 /// * `airdropped_tokens`: The number of tokens to be airdropped.
 /// * `vesting_period`: The duration of the vesting period in seconds.
+/// * `gamma_m`: The number of meme tokens sold on the bonding curve.
+/// * `omega_m`: The number of meme tokens reserved for the AMM liquidity.
+/// * `price_factor_num`, `price_factor_denom`: The price factor of the LP tokens.
+/// * `liquid`: Whether swaps pay out meme tokens instead of meme tickets.
 ///
 /// # Returns
 ///
@@ -139,16 +219,35 @@ impl<'info> NewPool<'info> {
 /// Sam's Journey: Creating a New Token Pool
 ///
 /// Sam wants to:
-/// 1. Create 1B total tokens
-/// 2. Set aside up to 100M for airdrops
+/// 1. Create 1B total tokens, e.g. 690M for trading and 310M for LP
+/// 2. Set aside up to 100M of the LP tokens for airdrops
 /// 3. Set a 7-day vesting period
 /// 4. Configure automated trading
+/// 5. Optionally buy the first tokens herself before anyone else can
 ///
 /// # Parameters
 /// * `ctx` - The context containing all necessary accounts
 /// * `airdropped_tokens` - Amount of tokens for airdrop (max 100M)
 /// * `vesting_period` - Time tokens are locked (in seconds)
-pub fn handle(ctx: Context<NewPool>, airdropped_tokens: u64, vesting_period: i64) -> Result<()> {
+/// * `gamma_m` - Tokens sold on the curve (`DEFAULT_MAX_M` by default)
+/// * `omega_m` - Tokens for LP (`DEFAULT_MAX_M_LP` by default)
+/// * `price_factor_num` - Price factor numerator (`DEFAULT_PRICE_FACTOR_NUMERATOR` by default)
+/// * `price_factor_denom` - Price factor denominator (`DEFAULT_PRICE_FACTOR_DENOMINATOR` by default)
+/// * `initial_buy_quote` - Quote to spend on a dev-buy at the starting price, including any
+///   transfer fee of the quote mint, capped by the quote mint's `DevBuyConfig`
+/// * `liquid` - Trade plain meme tokens from token accounts, without the lock and vesting
+///   of meme tickets
+pub fn handle(
+    ctx: Context<NewPool>,
+    airdropped_tokens: u64,
+    vesting_period: i64,
+    gamma_m: u64,
+    omega_m: u64,
+    price_factor_num: u64,
+    price_factor_denom: u64,
+    initial_buy_quote: Option<u64>,
+    liquid: bool,
+) -> Result<()> {
     let accs = ctx.accounts;
 
     // Chapter 1: Initial Checks 🔍
@@ -157,8 +256,21 @@ pub fn handle(ctx: Context<NewPool>, airdropped_tokens: u64, vesting_period: i64
         return Err(error!(AmmError::NonZeroInitialMemeSupply));
     }
 
-    // Ensure we're not airdropping too many tokens
-    if airdropped_tokens > MAX_AIRDROPPED_TOKENS {
+    // Ensure Sam's supply split leaves tokens on both sides
+    check_supply_split(gamma_m as u128, omega_m as u128, price_factor_denom)?;
+
+    // The whole supply has to fit into the mint
+    let total_supply = gamma_m
+        .checked_add(omega_m)
+        .ok_or(AmmError::MathOverflow)?;
+
+    // Liquid pools don't issue the tickets airdrops are claimed into
+    if liquid && airdropped_tokens > 0 {
+        return Err(error!(err::arg("Liquid pools can't reserve tokens for an airdrop")));
+    }
+
+    // Ensure we're not airdropping too many tokens, they come out of the LP share
+    if airdropped_tokens > MAX_AIRDROPPED_TOKENS || airdropped_tokens > omega_m {
         return Err(error!(AmmError::AirdroppedTokensOvercap));
     }
 
@@ -168,6 +280,10 @@ pub fn handle(ctx: Context<NewPool>, airdropped_tokens: u64, vesting_period: i64
         return Err(error!(AmmError::InvalidVestingPeriod));
     }
 
+    // Reject Token-2022 mints with extensions that could drain or freeze vaults
+    token_ext::validate_mint_extensions(&accs.meme_mint.to_account_info())?;
+    token_ext::validate_mint_extensions(&accs.quote_mint.to_account_info())?;
+
     // Chapter 2: Minting Meme Tokens 💰
     // Prepare the seeds for the pool signer PDA
     let seeds = &[
@@ -178,12 +294,11 @@ pub fn handle(ctx: Context<NewPool>, airdropped_tokens: u64, vesting_period: i64
 
     let signer_seeds = &[&seeds[..]];
 
-    // Mint all 1B tokens to pool vault
-    token::mint_to(
+    // Mint the whole supply to pool vault
+    token_interface::mint_to(
         accs.mint_meme_tokens().with_signer(signer_seeds),
-        MAX_MEME_TOKENS as u64, // 1B total tokens
-    )
-    .unwrap();
+        total_supply, // e.g. 1B total tokens
+    )?;
 
     // Chapter 3: Configuring Pool Settings ⚙️
     let pool = &mut accs.pool;
@@ -208,14 +323,12 @@ pub fn handle(ctx: Context<NewPool>, airdropped_tokens: u64, vesting_period: i64
     // Calculate SOL decimal precision (1B = 1 SOL)
     let mint_decimals = 10_u128
         .checked_pow(accs.quote_mint.decimals as u32)
-        .unwrap();
+        .ok_or(AmmError::MathOverflow)?;
     
     // Configure bonding curve parameters
     let gamma_s = accs.target_config.token_target_amount as u128;  // SOL target
-    let gamma_m = DEFAULT_MAX_M;        // 690M trading tokens
-    let omega_m = DEFAULT_MAX_M_LP;     // 310M LP tokens
-    let price_factor_num = DEFAULT_PRICE_FACTOR_NUMERATOR;     // Price adjustment
-    let price_factor_denom = DEFAULT_PRICE_FACTOR_DENOMINATOR; // factors
+    let gamma_m = gamma_m as u128;      // e.g. 690M trading tokens
+    let omega_m = omega_m as u128;      // e.g. 310M LP tokens
 
     // Calculate price curve slope (α)
     let (alpha_abs, decimals) = compute_alpha_abs(
@@ -244,7 +357,7 @@ pub fn handle(ctx: Context<NewPool>, airdropped_tokens: u64, vesting_period: i64
 
     // Chapter 6: Setting Up Token Distribution 🎁
     // Configure token reserve
-    pool.meme_reserve.tokens = DEFAULT_MAX_M as u64;  // 690M for trading
+    pool.meme_reserve.tokens = gamma_m as u64;        // e.g. 690M for trading
     pool.meme_reserve.mint = accs.meme_mint.key();    // Token mint address
     pool.meme_reserve.vault = accs.meme_vault.key();  // Token vault address
 
@@ -253,6 +366,90 @@ pub fn handle(ctx: Context<NewPool>, airdropped_tokens: u64, vesting_period: i64
     pool.creator_addr = accs.sender.key();      // Sam is the creator
     pool.airdropped_tokens = airdropped_tokens; // Set airdrop amount
     pool.vesting_period = vesting_period;       // Set vesting time
+    pool.supply_fixed = false;                  // Until create_metadata or finalize_mint
+    pool.liquid = liquid;                       // Tokens instead of tickets
+
+    // Chapter 7: Sam's Dev-Buy 🛒
+    // Buy in the same transaction, so nobody can front-run the creator
+    if let Some(initial_buy_quote) = initial_buy_quote {
+        let (creator_quote, quote_token_program) =
+            match (&accs.creator_quote, &accs.quote_token_program) {
+                (Some(creator_quote), Some(quote_token_program)) => {
+                    (creator_quote, quote_token_program)
+                }
+                _ => {
+                    return Err(error!(err::acc(
+                        "Creator quote account and quote token program are required to dev-buy"
+                    )))
+                }
+            };
+
+        // Only what reaches the vault after the quote mint's transfer fee is swapped
+        let quote_mint_info = accs.quote_mint.to_account_info();
+        let transfer_fee = token_ext::transfer_fee(&quote_mint_info, initial_buy_quote)?;
+
+        let fees = accs.pool.fees;
+        let swap_amount = accs
+            .pool
+            .execute_buy(
+                initial_buy_quote - transfer_fee,
+                0,
+                &fees,
+                &PriceLimit::default(),
+                Clock::get()?.slot,
+            )?;
+
+        // Cap the dev-buy to the admin set share of the trading tokens
+        let max_dev_buy = (gamma_m as u64)
+            .mul_div_floor(DevBuyConfig::max_bps(accs.dev_buy_config.as_deref()), BPS_DENOM)
+            .ok_or(AmmError::MathOverflow)?;
+
+        if swap_amount.amount_out + swap_amount.admin_fee_out > max_dev_buy {
+            return Err(error!(AmmError::DevBuyOvercap));
+        }
+
+        // Pay for the dev-buy, grossed up so the vault receives the full amount
+        let amount_to_send = token_ext::amount_with_transfer_fee(
+            &quote_mint_info,
+            swap_amount.amount_in + swap_amount.admin_fee_in,
+        )?;
+
+        token_interface::transfer_checked(
+            accs.send_creator_tokens(creator_quote, quote_token_program),
+            amount_to_send,
+            accs.quote_mint.decimals,
+        )?;
+
+        accs.quote_vault.reload()?;
+        accs.pool.check_vault_invariant(accs.quote_vault.amount)?;
+
+        if liquid {
+            let creator_meme = accs
+                .creator_meme
+                .as_ref()
+                .ok_or_else(|| err::acc("Creator meme account is required to dev-buy"))?;
+
+            token_interface::transfer_checked(
+                accs.send_meme_to_creator(creator_meme).with_signer(signer_seeds),
+                swap_amount.amount_out,
+                accs.meme_mint.decimals,
+            )?;
+        } else {
+            let pool_key = accs.pool.key();
+            let creator_ticket = accs
+                .creator_ticket
+                .as_mut()
+                .ok_or_else(|| err::acc("Creator ticket is required to dev-buy"))?;
+            creator_ticket.setup(pool_key, accs.sender.key(), swap_amount.amount_out)?;
+            accs.pool.hold_ticket_tokens(swap_amount.amount_out)?;
+        }
+
+        msg!(
+            "dev_buy_in: {}\n dev_buy_out: {}",
+            swap_amount.amount_in,
+            swap_amount.amount_out
+        );
+    }
 
     Ok(())
 }
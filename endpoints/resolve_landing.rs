@@ -0,0 +1,96 @@
+use crate::models::board::{Board, PlayerState, PropertyState, TileKind};
+use crate::models::bound::BoundPool;
+use crate::MonopolyError;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Accounts required to pay the rent or tax of the tile a player landed on.
+#[derive(Accounts)]
+pub struct ResolveLanding<'info> {
+    #[account(has_one = pool)]
+    pub board: Account<'info, Board>,
+    pub pool: Account<'info, BoundPool>,
+    #[account(mut, has_one = board, has_one = owner)]
+    pub player_state: Account<'info, PlayerState>,
+    /// State of the landed tile, may be uninitialized if nobody staked into it
+    /// CHECK: pda, deserialized in the handler when initialized
+    #[account(
+        mut,
+        seeds = [
+            PropertyState::PROPERTY_STATE_PREFIX,
+            board.key().as_ref(),
+            player_state.position.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub property_state: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = board.rent_vault == rent_vault.key()
+    )]
+    pub rent_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = pool.fee_vault_quote == fee_quote_vault.key()
+    )]
+    pub fee_quote_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user_sol: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ResolveLanding<'info> {
+    fn send_user_tokens(
+        &self,
+        to: &Account<'info, TokenAccount>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.user_sol.to_account_info(),
+            to: to.to_account_info(),
+            authority: self.owner.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Applies the effect of the tile the player landed on.
+///
+/// Landing on a staked property pays rent to its stakers through the board's
+/// rent vault, landing on a tax tile pays the tax into the pool's fee vault.
+/// Properties nobody staked into are free to land on.
+pub fn handle(ctx: Context<ResolveLanding>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    if !accs.player_state.pending_landing {
+        return Err(error!(MonopolyError::NoPendingLanding));
+    }
+
+    let tile = *accs.board.tile(accs.player_state.position)?;
+    let amount = Board::dollars_to_quote(tile.landing_fee()?, accs.pool.config.decimals.quote)?;
+
+    let mut paid = 0;
+
+    if amount > 0 && tile.is_stakeable() && !accs.property_state.data_is_empty() {
+        let property_info = accs.property_state.to_account_info();
+        let mut property_state: Account<PropertyState> = Account::try_from(&property_info)?;
+
+        if property_state.distribute_rent(amount)? {
+            token::transfer(accs.send_user_tokens(&accs.rent_vault), amount)?;
+            property_state.exit(ctx.program_id)?;
+            paid = amount;
+        }
+    } else if amount > 0 && tile.kind == TileKind::Tax {
+        token::transfer(accs.send_user_tokens(&accs.fee_quote_vault), amount)?;
+        paid = amount;
+    }
+
+    let player = &mut accs.player_state;
+    player.pending_landing = false;
+
+    msg!("position: {}\n paid: {}", player.position, paid);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::models::board::{Board, PlayerState};
+use crate::models::dice::{slot_hash_after, CommitReveal, DiceSource};
+use crate::MonopolyError;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::slot_hashes;
+
+/// Accounts required to reveal a committed roll and move the player.
+#[derive(Accounts)]
+pub struct RollDice<'info> {
+    pub board: Account<'info, Board>,
+    #[account(mut, has_one = board, has_one = owner)]
+    pub player_state: Account<'info, PlayerState>,
+    pub owner: Signer<'info>,
+    /// CHECK: the slot hashes sysvar, too large to deserialize, read raw
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
+
+/// Reveals the committed secret, rolls the dice and moves the player.
+///
+/// The roll has to be revealed after the slot following the commitment was
+/// hashed and within `REVEAL_WINDOW_SLOTS` of the commitment. Chance cards and
+/// jail are applied right away, rent and tax of the tile the player ends up on
+/// are paid with `resolve_landing`.
+///
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `secret` - Secret whose hash was committed with `commit_roll`
+pub fn handle(ctx: Context<RollDice>, secret: [u8; 32]) -> Result<()> {
+    let accs = ctx.accounts;
+    let player = &mut accs.player_state;
+    let board = &accs.board;
+
+    if !player.has_commitment() {
+        return Err(error!(MonopolyError::NoRollCommitted));
+    }
+
+    let slot_hash = slot_hash_after(&accs.slot_hashes.try_borrow_data()?, player.commit_slot)?
+        .ok_or(error!(MonopolyError::RevealTooEarly))?;
+
+    let source = CommitReveal {
+        commitment: &player.commitment,
+        secret: &secret,
+        slot_hash: &slot_hash,
+        player: player.key(),
+        commit_slot: player.commit_slot,
+        reveal_slot: Clock::get()?.slot,
+    };
+    let roll = source.roll()?;
+
+    player.commitment = [0; 32];
+    player.advance(board, &roll)?;
+
+    msg!(
+        "rolled: {} + {}\n position: {}\n in_jail: {}",
+        roll.first,
+        roll.second,
+        player.position,
+        player.in_jail
+    );
+
+    Ok(())
+}
@@ -0,0 +1,212 @@
+use crate::err::AmmError;
+use crate::models::board::{Board, PropertyStake, PropertyState, StakerPosition};
+use crate::models::bound::BoundPool;
+use crate::MonopolyError;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Account validation struct for selling a property stake back into the curve
+///
+/// # Account Requirements
+/// * `board` - The board the property belongs to
+/// * `pool` - The mutable bonding curve pool account of the board
+/// * `property_state` - Aggregate state of the staked property
+/// * `property_stake` - The stake being sold, closed to the owner
+/// * `staker_position` - Total stake of the owner in the property
+/// * `challenger_position` - Optional total stake of another staker, who takes
+///   over the property if the seller's total falls below theirs
+/// * `quote_vault` - The pool's SOL vault account
+/// * `rent_vault` - The board's rent vault, pays out unclaimed rent
+/// * `user_sol` - The user's SOL token account to receive swapped tokens
+/// * `owner` - The signer/owner of the stake
+/// * `pool_signer` - PDA with authority over pool accounts
+/// * `board_signer` - PDA with authority over the rent vault
+/// * `token_program` - The Solana Token Program
+#[derive(Accounts)]
+pub struct SellPropertyStake<'info> {
+    #[account(mut, has_one = pool)]
+    pub board: Account<'info, Board>,
+    #[account(mut)]
+    pub pool: Account<'info, BoundPool>,
+    #[account(
+        mut,
+        seeds = [
+            PropertyState::PROPERTY_STATE_PREFIX,
+            board.key().as_ref(),
+            property_stake.property_position.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub property_state: Account<'info, PropertyState>,
+    #[account(mut, has_one = board, has_one = owner, close = owner)]
+    pub property_stake: Account<'info, PropertyStake>,
+    #[account(
+        mut,
+        seeds = [
+            StakerPosition::STAKER_POSITION_PREFIX,
+            board.key().as_ref(),
+            property_stake.property_position.to_le_bytes().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump
+    )]
+    pub staker_position: Account<'info, StakerPosition>,
+    #[account(
+        has_one = board,
+        constraint = challenger_position.property_position == property_stake.property_position,
+        constraint = challenger_position.owner != owner.key()
+    )]
+    pub challenger_position: Option<Account<'info, StakerPosition>>,
+    #[account(
+        mut,
+        constraint = pool.quote_reserve.vault == quote_vault.key()
+    )]
+    pub quote_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = board.rent_vault == rent_vault.key()
+    )]
+    pub rent_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user_sol: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: pda signer
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    pub pool_signer: AccountInfo<'info>,
+    /// CHECK: board pda signer
+    #[account(seeds = [Board::SIGNER_PDA_PREFIX, board.key().as_ref()], bump)]
+    pub board_signer: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> SellPropertyStake<'info> {
+    fn send_tokens_to_user(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.quote_vault.to_account_info(),
+            to: self.user_sol.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    fn send_rent_to_user(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.rent_vault.to_account_info(),
+            to: self.user_sol.to_account_info(),
+            authority: self.board_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Sells the whole stake back into the bonding curve and closes it
+///
+/// The staked meme tokens go through the same sell path as `swap_x`, any rent
+/// the stake accrued and didn't claim yet is paid out alongside.
+///
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `coin_y_min_value` - The minimum amount of SOL to receive (slippage protection)
+///
+/// # Errors
+/// * `MonopolyError::PropertyStakeLocked` - If the stake's lock time hasn't passed
+/// * `AmmError::PoolIsLocked` - If the pool is currently locked
+pub fn handle(ctx: Context<SellPropertyStake>, coin_y_min_value: u64) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let current_ts = Clock::get()?.unix_timestamp;
+
+    if !accs.property_stake.is_unlocked(current_ts) {
+        return Err(error!(MonopolyError::PropertyStakeLocked));
+    }
+
+    if accs.pool.locked {
+        return Err(error!(AmmError::PoolIsLocked));
+    }
+
+    let stake_amount = accs.property_stake.stake_amount;
+    let rent = accs.property_stake.pending_rent(&accs.property_state)?;
+
+    let pool_state = &mut accs.pool;
+
+    let fees = pool_state.sell_fees(&pool_state.fees, Clock::get()?.slot)?;
+    let swap_amount =
+        pool_state.swap_amounts_with_fees(stake_amount, coin_y_min_value, false, &fees)?;
+
+    pool_state.apply_swap(&swap_amount, false)?;
+    pool_state.release_ticket_tokens(stake_amount);
+
+    let property_state = &mut accs.property_state;
+    property_state.total_investment = property_state
+        .total_investment
+        .checked_sub(stake_amount)
+        .ok_or(AmmError::InvariantViolation)?;
+    property_state.stake_count = property_state
+        .stake_count
+        .checked_sub(1)
+        .ok_or(AmmError::InvariantViolation)?;
+
+    let staker_position = &mut accs.staker_position;
+    staker_position.total_stake = staker_position
+        .total_stake
+        .checked_sub(stake_amount)
+        .ok_or(AmmError::InvariantViolation)?;
+
+    // The seller keeps the property while their remaining total clears the
+    // threshold, a larger remaining staker can take it over right away
+    let tile = *accs.board.tile(property_state.position)?;
+    if property_state.release_ownership(&tile, staker_position)? {
+        accs.board.owners[property_state.position as usize] = Pubkey::default();
+    }
+
+    if let Some(challenger) = &accs.challenger_position {
+        if property_state.try_claim_ownership(&tile, challenger)? {
+            accs.board.owners[property_state.position as usize] = challenger.owner;
+        }
+    }
+
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &accs.pool.key().to_bytes()[..],
+        &[ctx.bumps.pool_signer],
+    ];
+
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        accs.send_tokens_to_user().with_signer(signer_seeds),
+        swap_amount.amount_out,
+    )?;
+
+    accs.quote_vault.reload()?;
+    accs.pool.check_vault_invariant(accs.quote_vault.amount)?;
+
+    if rent > 0 {
+        let board_seeds = &[
+            Board::SIGNER_PDA_PREFIX,
+            &accs.board.key().to_bytes()[..],
+            &[ctx.bumps.board_signer],
+        ];
+
+        let board_signer_seeds = &[&board_seeds[..]];
+
+        token::transfer(
+            accs.send_rent_to_user().with_signer(board_signer_seeds),
+            rent,
+        )?;
+    }
+
+    msg!(
+        "swapped_in: {}\n swapped_out: {}\n rent_claimed: {}",
+        swap_amount.amount_in,
+        swap_amount.amount_out,
+        rent
+    );
+
+    Ok(())
+}
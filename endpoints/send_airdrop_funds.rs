@@ -4,8 +4,7 @@ use crate::models::staking::StakingPool;
 use anchor_lang::context::{Context, CpiContext};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token;
-use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use solana_program::account_info::AccountInfo;
 
 #[derive(Accounts)]
@@ -20,32 +19,34 @@ pub struct SendAirdropFunds<'info> {
     #[account(mut, seeds = [StakingPool::SIGNER_PDA_PREFIX, staking.key().as_ref()], bump)]
     pub staking_pool_signer_pda: AccountInfo<'info>,
     #[account(mut)]
-    pub staking_meme_vault: Box<Account<'info, TokenAccount>>,
+    pub staking_meme_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         constraint = meme_mint.key() == staking_meme_vault.mint
             @ err::acc("Invalid meme mint")
     )]
-    pub meme_mint: Box<Account<'info, Mint>>,
+    pub meme_mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(
         init,
         payer = sender,
         associated_token::mint = meme_mint,
-        associated_token::authority = airdrop_owner
+        associated_token::authority = airdrop_owner,
+        associated_token::token_program = token_program
     )]
-    pub airdrop_token_vault: Box<Account<'info, TokenAccount>>,
+    pub airdrop_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(constraint = airdrop_owner.key() == SWAP_AUTH_KEY)]
     /// CHECK: constraint
     pub airdrop_owner: AccountInfo<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 impl<'info> SendAirdropFunds<'info> {
-    fn transfer_airdrop_meme_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
-        let cpi_accounts = Transfer {
+    fn transfer_airdrop_meme_ctx(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
             from: self.staking_meme_vault.to_account_info(),
+            mint: self.meme_mint.to_account_info(),
             to: self.airdrop_token_vault.to_account_info(),
             authority: self.staking_pool_signer_pda.to_account_info(),
         };
@@ -65,14 +66,16 @@ pub fn handle(ctx: Context<SendAirdropFunds>) -> Result<()> {
 
     let staking_signer_seeds = &[&staking_seeds[..]];
 
-    accs.staking.to_airdrop = 0;
+    let to_airdrop = accs.staking.to_airdrop;
 
-    token::transfer(
+    token_interface::transfer_checked(
         accs.transfer_airdrop_meme_ctx()
             .with_signer(staking_signer_seeds),
-        accs.staking.to_airdrop,
-    )
-    .unwrap();
+        to_airdrop,
+        accs.meme_mint.decimals,
+    )?;
+
+    accs.staking.to_airdrop = 0;
 
     Ok(())
 }
@@ -0,0 +1,55 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::airdrop::AirdropDistributor;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetAirdropRoot<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    #[account(
+        constraint = pool.creator_addr == sender.key()
+            @ err::acc("Only the pool creator can set the airdrop root"),
+    )]
+    pub pool: Account<'info, BoundPool>,
+    #[account(
+        init,
+        payer = sender,
+        space = AirdropDistributor::space(),
+        seeds = [AirdropDistributor::AIRDROP_PREFIX, pool.key().as_ref()],
+        bump
+    )]
+    pub distributor: Account<'info, AirdropDistributor>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets up the Merkle distributor of the pool's airdropped tokens.
+///
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `merkle_root` - Root of the tree of `(index, claimant, amount)` leaves
+/// * `expiry_ts` - Unix timestamp after which unclaimed tokens can be clawed back
+pub fn handle(ctx: Context<SetAirdropRoot>, merkle_root: [u8; 32], expiry_ts: i64) -> Result<()> {
+    let accs = ctx.accounts;
+
+    if accs.pool.airdropped_tokens == 0 {
+        return Err(error!(AmmError::NoAirdropTokens));
+    }
+
+    if expiry_ts <= Clock::get()?.unix_timestamp {
+        return Err(error!(err::arg("Airdrop expiry must be in the future")));
+    }
+
+    let distributor = &mut accs.distributor;
+
+    distributor.pool = accs.pool.key();
+    distributor.merkle_root = merkle_root;
+    distributor.total_amount = accs.pool.airdropped_tokens;
+    distributor.claimed_amount = 0;
+    distributor.num_claimed = 0;
+    distributor.expiry_ts = expiry_ts;
+    distributor.clawed_back = false;
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use crate::consts::ADMIN_KEY;
+use crate::err;
+use crate::models::bound::BPS_DENOM;
+use crate::models::dev_buy_config::DevBuyConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct SetDevBuyCap<'info> {
+    #[account(
+        mut,
+        constraint = sender.key() == ADMIN_KEY
+            @ err::acc("Only the admin can set the dev-buy cap")
+    )]
+    pub sender: Signer<'info>,
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = DevBuyConfig::space(),
+        seeds = [DevBuyConfig::DEV_BUY_PREFIX, quote_mint.key().as_ref()],
+        bump
+    )]
+    pub dev_buy_config: Account<'info, DevBuyConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets the share of the trading tokens creators can dev-buy on new pools of
+/// the quote mint.
+///
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `max_bps` - The new cap in basis points of the trading tokens
+pub fn handle(ctx: Context<SetDevBuyCap>, max_bps: u64) -> Result<()> {
+    if max_bps > BPS_DENOM {
+        return Err(error!(err::arg("Dev-buy cap can't exceed all trading tokens")));
+    }
+
+    let config = &mut ctx.accounts.dev_buy_config;
+
+    config.quote_mint = ctx.accounts.quote_mint.key();
+    config.max_bps = max_bps;
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::consts::ADMIN_KEY;
+use crate::err;
+use crate::err::AmmError;
+use crate::models::fee_discount::{FeeDiscountConfig, FeeDiscountTier, MAX_DISCOUNT_TIERS};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFeeDiscount<'info> {
+    #[account(
+        mut,
+        constraint = sender.key() == ADMIN_KEY
+            @ err::acc("Only the admin can set the fee discount schedule")
+    )]
+    pub sender: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = FeeDiscountConfig::space(),
+        seeds = [FeeDiscountConfig::FEE_DISCOUNT_PREFIX],
+        bump
+    )]
+    pub fee_discount_config: Account<'info, FeeDiscountConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Replaces the points-burn discount schedule applied to swap quote fees.
+///
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `tiers` - The new schedule, unused tiers must have a zero discount
+pub fn handle(
+    ctx: Context<SetFeeDiscount>,
+    tiers: [FeeDiscountTier; MAX_DISCOUNT_TIERS],
+) -> Result<()> {
+    let config = &mut ctx.accounts.fee_discount_config;
+
+    config.tiers = tiers;
+
+    if !config.validate() {
+        return Err(error!(AmmError::InvalidFeeDiscountSchedule));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Accounts required to move unaccounted quote tokens out of a pool's vault.
+///
+/// Anyone can skim, the surplus can only ever go to the pool's fee vault.
+#[derive(Accounts)]
+pub struct SkimPool<'info> {
+    pub pool: Account<'info, BoundPool>,
+    #[account(
+        mut,
+        constraint = pool.quote_reserve.vault == quote_vault.key()
+    )]
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(constraint = pool.meme_reserve.vault == meme_vault.key())]
+    pub meme_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = pool.fee_vault_quote == fee_quote_vault.key()
+    )]
+    pub fee_quote_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(constraint = pool.quote_reserve.mint == quote_mint.key())]
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: pda signer
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    pub pool_signer: AccountInfo<'info>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SkimPool<'info> {
+    fn send_surplus_to_fee_vault(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from: self.quote_vault.to_account_info(),
+            mint: self.quote_mint.to_account_info(),
+            to: self.fee_quote_vault.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.quote_token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Reconciles the quote vault with the pool's accounting.
+///
+/// Quote tokens in the vault beyond the reserve and admin fees, e.g. tokens
+/// sent straight to the vault, are routed to the fee vault. Fails if the vault
+/// holds less than the pool accounts for.
+///
+/// The meme vault is checked for a deficit the same way. Its surplus stays in
+/// place, the fee vault only takes quote tokens.
+pub fn handle(ctx: Context<SkimPool>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let surplus = accs.pool.quote_vault_surplus(accs.quote_vault.amount)?;
+    accs.pool.meme_vault_surplus(accs.meme_vault.amount)?;
+
+    if surplus > 0 {
+        let seeds = &[
+            BoundPool::SIGNER_PDA_PREFIX,
+            &accs.pool.key().to_bytes()[..],
+            &[ctx.bumps.pool_signer],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::transfer_checked(
+            accs.send_surplus_to_fee_vault().with_signer(signer_seeds),
+            surplus,
+            accs.quote_mint.decimals,
+        )?;
+    }
+
+    msg!("quote_surplus_skimmed: {}", surplus);
+
+    Ok(())
+}
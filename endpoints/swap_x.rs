@@ -1,8 +1,13 @@
+use crate::consts::POINTS_MINT;
+use crate::err;
 use crate::err::AmmError;
-use crate::models::bound::BoundPool;
+use crate::models::bound::{check_deadline, BoundPool, PriceLimit};
+use crate::models::fee_discount::{discounted_fees, FeeDiscountConfig};
 use crate::models::staked_lp::MemeTicket;
+use crate::token_ext;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, spl_token, Burn, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{self, CloseAccount, TokenInterface, TransferChecked};
 
 /// Account validation struct for swapping meme tokens for SOL
 /// 
@@ -11,12 +16,21 @@ use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 ///
 /// # Account Requirements
 /// * `pool` - The mutable bonding curve pool account
-/// * `meme_ticket` - The user's meme token ticket account, must be owned by signer
+/// * `meme_ticket` - The user's meme token ticket account, must be owned by
+///   signer, required unless the pool is liquid
 /// * `user_sol` - The user's SOL token account to receive swapped tokens
 /// * `quote_vault` - The pool's SOL vault account
+/// * `quote_mint` - The pool's quote mint, SPL Token or Token-2022
 /// * `owner` - The signer/owner of the meme ticket
 /// * `pool_signer` - PDA with authority over pool accounts
-/// * `token_program` - The Solana Token Program
+/// * `user_points` - Optional points account to burn from for a fee discount
+/// * `points_mint` - Optional points mint, required when burning points
+/// * `fee_discount_config` - Optional points-burn discount schedule
+/// * `token_program` - The Solana Token Program, used for points
+/// * `quote_token_program` - The token program of the quote mint
+/// * `meme_vault`, `meme_mint`, `user_meme`, `meme_token_program` - The
+///   pool's meme vault and mint, the user's meme token account to sell from
+///   and the meme mint's token program, required if the pool is liquid
 #[derive(Accounts)]
 pub struct SwapCoinX<'info> {
     #[account(mut)]
@@ -26,19 +40,49 @@ pub struct SwapCoinX<'info> {
         has_one = pool,
         has_one = owner
     )]
-    pub meme_ticket: Account<'info, MemeTicket>,
+    pub meme_ticket: Option<Account<'info, MemeTicket>>,
     #[account(mut)]
-    pub user_sol: Account<'info, TokenAccount>,
+    pub user_sol: InterfaceAccount<'info, token_interface::TokenAccount>,
     #[account(
         mut,
         constraint = pool.quote_reserve.vault == quote_vault.key()
     )]
-    pub quote_vault: Account<'info, TokenAccount>,
+    pub quote_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(constraint = pool.quote_reserve.mint == quote_mint.key())]
+    pub quote_mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(mut)]
     pub owner: Signer<'info>,
     /// CHECK: pda signer
     #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
     pub pool_signer: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = user_points.mint == POINTS_MINT
+            @ err::acc("User points account must be of points mint"),
+        constraint = user_points.owner == owner.key()
+            @ err::acc("User points account must be owned by the signer"),
+    )]
+    pub user_points: Option<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = points_mint.key() == POINTS_MINT)]
+    pub points_mint: Option<Account<'info, Mint>>,
+    #[account(seeds = [FeeDiscountConfig::FEE_DISCOUNT_PREFIX], bump)]
+    pub fee_discount_config: Option<Account<'info, FeeDiscountConfig>>,
     pub token_program: Program<'info, Token>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    #[account(
+        mut,
+        constraint = pool.meme_reserve.vault == meme_vault.key()
+    )]
+    pub meme_vault: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(constraint = pool.meme_reserve.mint == meme_mint.key())]
+    pub meme_mint: Option<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        mut,
+        constraint = user_meme.mint == pool.meme_reserve.mint
+            @ err::acc("User meme account must be of meme mint"),
+    )]
+    pub user_meme: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    pub meme_token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 impl<'info> SwapCoinX<'info> {
@@ -49,16 +93,90 @@ impl<'info> SwapCoinX<'info> {
     ///
     /// # Returns
     /// * `CpiContext` - The context for the token transfer CPI
-    fn send_tokens_to_user(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
-        let cpi_accounts = Transfer {
+    fn send_tokens_to_user(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
             from: self.quote_vault.to_account_info(),
+            mint: self.quote_mint.to_account_info(),
             to: self.user_sol.to_account_info(),
             authority: self.pool_signer.to_account_info(),
         };
 
-        let cpi_program = self.token_program.to_account_info();
+        let cpi_program = self.quote_token_program.to_account_info();
         CpiContext::new(cpi_program, cpi_accounts)
     }
+
+    /// Creates a CPI context for transferring meme tokens from the user to the
+    /// meme vault of a liquid pool
+    ///
+    /// # Returns
+    /// * `Result<CpiContext>` - The context for the token transfer CPI, or an
+    ///   error if the meme accounts weren't provided
+    fn send_user_meme(&self) -> Result<CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>> {
+        let (meme_vault, meme_mint, user_meme, meme_token_program) = match (
+            &self.meme_vault,
+            &self.meme_mint,
+            &self.user_meme,
+            &self.meme_token_program,
+        ) {
+            (Some(meme_vault), Some(meme_mint), Some(user_meme), Some(meme_token_program)) => {
+                (meme_vault, meme_mint, user_meme, meme_token_program)
+            }
+            _ => {
+                return Err(error!(err::acc(
+                    "Meme accounts and token program are required to sell into a liquid pool"
+                )))
+            }
+        };
+
+        let cpi_accounts = TransferChecked {
+            from: user_meme.to_account_info(),
+            mint: meme_mint.to_account_info(),
+            to: meme_vault.to_account_info(),
+            authority: self.owner.to_account_info(),
+        };
+
+        let cpi_program = meme_token_program.to_account_info();
+        Ok(CpiContext::new(cpi_program, cpi_accounts))
+    }
+
+    /// Creates a CPI context for closing the user's WSOL account
+    ///
+    /// Closing a wrapped SOL account unwraps its whole balance into native SOL
+    /// held by the owner.
+    ///
+    /// # Returns
+    /// * `CpiContext` - The context for the close account CPI
+    fn unwrap_user_sol(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        let cpi_accounts = CloseAccount {
+            account: self.user_sol.to_account_info(),
+            destination: self.owner.to_account_info(),
+            authority: self.owner.to_account_info(),
+        };
+
+        let cpi_program = self.quote_token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Creates a CPI context for burning the user's points
+    ///
+    /// # Returns
+    /// * `Result<CpiContext>` - The context for the burn CPI, or an error if
+    ///   the points accounts weren't provided
+    fn burn_user_points(&self) -> Result<CpiContext<'_, '_, '_, 'info, Burn<'info>>> {
+        let (points_mint, user_points) = match (&self.points_mint, &self.user_points) {
+            (Some(points_mint), Some(user_points)) => (points_mint, user_points),
+            _ => return Err(error!(err::acc("Points accounts are required to burn points"))),
+        };
+
+        let cpi_accounts = Burn {
+            mint: points_mint.to_account_info(),
+            from: user_points.to_account_info(),
+            authority: self.owner.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        Ok(CpiContext::new(cpi_program, cpi_accounts))
+    }
 }
 
 /// Handles the swap of meme tokens for SOL using the bonding curve
@@ -68,8 +186,21 @@ impl<'info> SwapCoinX<'info> {
 ///
 /// # Arguments
 /// * `ctx` - The context containing all required accounts
-/// * `coin_in_amount` - The amount of meme tokens to swap
-/// * `coin_y_min_value` - The minimum amount of SOL to receive (slippage protection)
+/// * `coin_in_amount` - The amount of meme tokens to swap, for a liquid pool
+///   including any transfer fee of the meme mint
+/// * `coin_y_min_value` - The minimum amount of SOL to receive after any
+///   transfer fee of the quote mint (slippage protection)
+/// * `points_to_burn` - Maximum amount of points to burn for a quote fee discount
+/// * `unwrap_sol` - Close `user_sol` after the swap to receive native SOL,
+///   `user_sol` must be a WSOL account of the signer
+/// * `min_avg_price` - Lowest average price to receive, in quote tokens per
+///   whole meme token scaled by `PRICE_PRECISION`
+/// * `max_price_impact_bps` - Highest spot price decrease caused by the sell
+/// * `deadline_ts` - Unix timestamp after which the sell fails
+///
+/// With a price bound the sell is partially filled up to the bound instead of
+/// failing, only the filled part is taken from the ticket, or from `user_meme`
+/// if the pool is liquid.
 ///
 /// # Returns
 /// * `Result<()>` - Result indicating success or containing error
@@ -79,39 +210,152 @@ impl<'info> SwapCoinX<'info> {
 /// * `AmmError::TicketTokensLocked` - If the meme tokens are still locked
 /// * `AmmError::NotEnoughTicketTokens` - If user has insufficient tokens
 /// * `AmmError::PoolIsLocked` - If the pool is currently locked
-pub fn handle(ctx: Context<SwapCoinX>, coin_in_amount: u64, coin_y_min_value: u64) -> Result<()> {
+/// * `AmmError::SlippageExceeded` - If less than the minimum is received
+/// * `AmmError::FeeDiscountUnavailable` - If burning points without the discount config
+/// * `AmmError::QuoteMintNotNative` - If unwrapping SOL on a pool not quoted in WSOL
+/// * `AmmError::PriceLimitExceeded` - If no part of the sell is within the price bounds
+/// * `AmmError::Expired` - If the transaction landed after `deadline_ts`
+pub fn handle(
+    ctx: Context<SwapCoinX>,
+    coin_in_amount: u64,
+    coin_y_min_value: u64,
+    points_to_burn: u64,
+    unwrap_sol: bool,
+    min_avg_price: Option<u128>,
+    max_price_impact_bps: Option<u64>,
+    deadline_ts: Option<i64>,
+) -> Result<()> {
     let accs = ctx.accounts;
 
-    if coin_in_amount == 0 {
-        return Err(error!(AmmError::NoZeroTokens));
+    check_deadline(deadline_ts, Clock::get()?.unix_timestamp)?;
+
+    // Liquid pools take tokens, all other pools tickets
+    if accs.pool.liquid {
+        if accs.meme_ticket.is_some() {
+            return Err(error!(err::acc("Liquid pools don't issue meme tickets")));
+        }
+
+        if accs.meme_vault.is_none()
+            || accs.meme_mint.is_none()
+            || accs.user_meme.is_none()
+            || accs.meme_token_program.is_none()
+        {
+            return Err(error!(err::acc(
+                "Meme accounts and token program are required to sell into a liquid pool"
+            )));
+        }
+    } else if accs.meme_ticket.is_none() {
+        return Err(error!(err::acc("Meme ticket is required to sell")));
     }
 
-    let user_ticket = &mut accs.meme_ticket;
+    if unwrap_sol {
+        if accs.pool.quote_reserve.mint != spl_token::native_mint::ID {
+            return Err(error!(AmmError::QuoteMintNotNative));
+        }
 
-    if !user_ticket.is_unlocked() {
-        return Err(error!(AmmError::TicketTokensLocked));
+        if accs.user_sol.owner != accs.owner.key() {
+            return Err(error!(err::acc("Only the signer's own WSOL account can be unwrapped")));
+        }
     }
 
-    if coin_in_amount > user_ticket.amount {
-        return Err(error!(AmmError::NotEnoughTicketTokens));
+    if coin_in_amount == 0 {
+        return Err(error!(AmmError::NoZeroTokens));
     }
 
+    let liquid = accs.pool.liquid;
+
+    // Meme tokens that reach the pool, a liquid pool's meme mint may withhold
+    // a transfer fee on the way
+    let meme_in_amount = if liquid {
+        let meme_mint = accs
+            .meme_mint
+            .as_ref()
+            .ok_or_else(|| err::acc("Meme mint is required to sell into a liquid pool"))?;
+
+        coin_in_amount - token_ext::transfer_fee(&meme_mint.to_account_info(), coin_in_amount)?
+    } else {
+        let user_ticket = accs
+            .meme_ticket
+            .as_ref()
+            .ok_or_else(|| err::acc("Meme ticket is required to sell"))?;
+
+        if user_ticket.airdrop {
+            return Err(error!(AmmError::AirdropTicketNotSellable));
+        }
+
+        if !user_ticket.is_unlocked()? {
+            return Err(error!(AmmError::TicketTokensLocked));
+        }
+
+        if coin_in_amount > user_ticket.amount {
+            return Err(error!(AmmError::NotEnoughTicketTokens));
+        }
+
+        coin_in_amount
+    };
+
     let pool_state = &mut accs.pool;
 
     if pool_state.locked {
         return Err(error!(AmmError::PoolIsLocked));
     }
 
-    let swap_amount = pool_state.swap_amounts(coin_in_amount, coin_y_min_value, false);
+    let (fees, discount_tier) = discounted_fees(
+        accs.fee_discount_config.as_deref(),
+        &pool_state.fees,
+        points_to_burn,
+    )?;
+    let fees = pool_state.sell_fees(&fees, Clock::get()?.slot)?;
+
+    let limit = PriceLimit {
+        avg_price: min_avg_price,
+        max_price_impact_bps,
+    };
+
+    let swap_amount = pool_state.limited_swap_amounts(
+        meme_in_amount,
+        coin_y_min_value,
+        false,
+        &fees,
+        &limit,
+    )?;
+
+    // Quote mints with a transfer fee deliver less than what leaves the vault
+    let transfer_fee =
+        token_ext::transfer_fee(&accs.quote_mint.to_account_info(), swap_amount.amount_out)?;
+
+    if swap_amount.amount_out.saturating_sub(transfer_fee) < coin_y_min_value {
+        return Err(error!(AmmError::SlippageExceeded));
+    }
+
+    pool_state.apply_swap(&swap_amount, false)?;
+
+    // A partial fill only takes what was sold
+    let meme_sold = swap_amount.amount_in + swap_amount.admin_fee_in;
 
-    pool_state.admin_fees_meme += swap_amount.admin_fee_in;
-    pool_state.admin_fees_quote += swap_amount.admin_fee_out;
+    if liquid {
+        let meme_mint = accs
+            .meme_mint
+            .as_ref()
+            .ok_or_else(|| err::acc("Meme mint is required to sell into a liquid pool"))?;
 
-    pool_state.meme_reserve.tokens += swap_amount.amount_in;
-    pool_state.quote_reserve.tokens -= swap_amount.amount_out + swap_amount.admin_fee_out;
+        // Grossed up so the vault receives exactly what was sold
+        let amount_to_send =
+            token_ext::amount_with_transfer_fee(&meme_mint.to_account_info(), meme_sold)?;
 
-    user_ticket.amount -= coin_in_amount;
-    user_ticket.vesting.notional -= coin_in_amount;
+        token_interface::transfer_checked(
+            accs.send_user_meme()?,
+            amount_to_send,
+            meme_mint.decimals,
+        )?;
+    } else {
+        accs.meme_ticket
+            .as_mut()
+            .ok_or_else(|| err::acc("Meme ticket is required to sell"))?
+            .debit(meme_sold)?;
+
+        accs.pool.release_ticket_tokens(meme_sold);
+    }
 
     let seeds = &[
         BoundPool::SIGNER_PDA_PREFIX,
@@ -121,11 +365,28 @@ pub fn handle(ctx: Context<SwapCoinX>, coin_in_amount: u64, coin_y_min_value: u6
 
     let signer_seeds = &[&seeds[..]];
 
-    token::transfer(
+    token_interface::transfer_checked(
         accs.send_tokens_to_user().with_signer(signer_seeds),
         swap_amount.amount_out,
-    )
-    .unwrap();
+        accs.quote_mint.decimals,
+    )?;
+
+    accs.quote_vault.reload()?;
+    accs.pool.check_vault_invariant(accs.quote_vault.amount)?;
+
+    if unwrap_sol {
+        token_interface::close_account(accs.unwrap_user_sol())?;
+    }
+
+    if let Some(tier) = discount_tier {
+        token::burn(accs.burn_user_points()?, tier.points_amount)?;
+
+        msg!(
+            "points_burned: {}\n fee_discount: {}",
+            tier.points_amount,
+            tier.discount
+        );
+    }
 
     msg!(
         "swapped_in: {}\n swapped_out: {}",
@@ -1,19 +1,30 @@
 /// Import necessary constants from the crate
 use crate::consts::{POINTS_MINT, POINTS_PDA};
-/// Import error handling
-use crate::err::AmmError;
 /// Import math utilities
 use crate::libraries::MulDiv;
 /// Import bonding curve pool model
-use crate::models::bound::BoundPool;
+use crate::models::bound::{check_deadline, BoundPool, PriceLimit};
+/// Import points-burn fee discount model
+use crate::models::fee_discount::{discounted_fees, FeeDiscountConfig};
 /// Import points epoch model
 use crate::models::points_epoch::PointsEpoch;
 /// Import staked LP model
 use crate::models::staked_lp::MemeTicket;
+/// Import Token-2022 extension helpers
+use crate::token_ext;
+/// Import error handling
+use crate::err;
+use crate::err::AmmError;
 /// Import Anchor lang prelude
 use anchor_lang::prelude::*;
+/// Import system program CPIs for wrapping native SOL
+use anchor_lang::system_program;
+/// Import associated token program for the user's WSOL account
+use anchor_spl::associated_token::AssociatedToken;
 /// Import SPL token program types
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, spl_token, Burn, Mint, Token, TokenAccount, Transfer};
+/// Import token interface types for SPL Token and Token-2022 quote mints
+use anchor_spl::token_interface::{self, SyncNative, TokenInterface, TransferChecked};
 /// Import min function for points calculation
 use std::cmp::min;
 
@@ -29,11 +40,25 @@ pub struct SwapCoinY<'info> {
         mut,
         constraint = pool.quote_reserve.vault == quote_vault.key()
     )]
-    quote_vault: Account<'info, TokenAccount>,
+    quote_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    /// The pool's quote mint, SPL Token or Token-2022
+    #[account(constraint = pool.quote_reserve.mint == quote_mint.key())]
+    quote_mint: InterfaceAccount<'info, token_interface::Mint>,
     /// The user's SOL token account that will send tokens
     #[account(mut)]
-    user_sol: Account<'info, TokenAccount>,
-    /// The user's meme ticket account that will be initialized
+    user_sol: InterfaceAccount<'info, token_interface::TokenAccount>,
+    /// The signer's WSOL associated token account, created if needed. When
+    /// passed, native SOL is wrapped into and paid from it instead of `user_sol`
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = quote_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = quote_token_program
+    )]
+    wsol_ata: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+    /// The user's meme ticket account that will be initialized, required
+    /// unless the pool is liquid
     #[account(
         init,
         payer = owner,
@@ -41,7 +66,26 @@ pub struct SwapCoinY<'info> {
         seeds = [pool.key().as_ref(), owner.key().as_ref(), _ticket_number.to_le_bytes().as_ref()],
         bump,
     )]
-    meme_ticket: Account<'info, MemeTicket>,
+    meme_ticket: Option<Account<'info, MemeTicket>>,
+    /// The pool's meme token vault, required if the pool is liquid
+    #[account(
+        mut,
+        constraint = pool.meme_reserve.vault == meme_vault.key()
+    )]
+    meme_vault: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    /// The pool's meme mint, required if the pool is liquid
+    #[account(constraint = pool.meme_reserve.mint == meme_mint.key())]
+    meme_mint: Option<InterfaceAccount<'info, token_interface::Mint>>,
+    /// The user's meme token account receiving the meme tokens, required if
+    /// the pool is liquid
+    #[account(
+        mut,
+        constraint = user_meme.mint == pool.meme_reserve.mint
+            @ err::acc("User meme account must be of meme mint"),
+        constraint = user_meme.owner == owner.key()
+            @ err::acc("User meme account must be owned by the signer"),
+    )]
+    user_meme: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
     /// The user's points token account that will receive points
     #[account(
         mut,
@@ -68,6 +112,9 @@ pub struct SwapCoinY<'info> {
         token::authority = points_pda
     )]
     points_acc: Account<'info, TokenAccount>,
+    /// Optional points-burn discount schedule, required when burning points
+    #[account(seeds = [FeeDiscountConfig::FEE_DISCOUNT_PREFIX], bump)]
+    fee_discount_config: Option<Account<'info, FeeDiscountConfig>>,
     /// The owner/signer of the transaction
     #[account(mut)]
     owner: Signer<'info>,
@@ -79,22 +126,98 @@ pub struct SwapCoinY<'info> {
     /// CHECK: pda signer
     #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
     pool_signer_pda: AccountInfo<'info>,
-    /// The SPL token program
+    /// The SPL token program, used for points
     token_program: Program<'info, Token>,
+    /// The token program of the quote mint
+    quote_token_program: Interface<'info, TokenInterface>,
+    /// The token program of the meme mint, required if the pool is liquid
+    meme_token_program: Option<Interface<'info, TokenInterface>>,
+    /// The associated token program, required with `wsol_ata`
+    associated_token_program: Option<Program<'info, AssociatedToken>>,
     /// The system program
     system_program: Program<'info, System>,
 }
 
 impl<'info> SwapCoinY<'info> {
+    /// The account the user pays from, the WSOL associated token account when
+    /// wrapping native SOL into it
+    fn quote_source(&self, native_sol: bool) -> AccountInfo<'info> {
+        match &self.wsol_ata {
+            Some(wsol_ata) if native_sol => wsol_ata.to_account_info(),
+            _ => self.user_sol.to_account_info(),
+        }
+    }
+
     /// Helper function to create CPI context for transferring SOL from user
-    fn send_user_tokens(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
-        let cpi_accounts = Transfer {
-            from: self.user_sol.to_account_info(),
+    fn send_user_tokens(
+        &self,
+        from: AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_accounts = TransferChecked {
+            from,
+            mint: self.quote_mint.to_account_info(),
             to: self.quote_vault.to_account_info(),
             authority: self.owner.to_account_info(),
         };
 
-        let cpi_program = self.token_program.to_account_info();
+        let cpi_program = self.quote_token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Helper function to create CPI context for transferring meme tokens from
+    /// the pool to the user of a liquid pool
+    fn send_meme_to_user(&self) -> Result<CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>> {
+        let (meme_vault, meme_mint, user_meme, meme_token_program) = match (
+            &self.meme_vault,
+            &self.meme_mint,
+            &self.user_meme,
+            &self.meme_token_program,
+        ) {
+            (Some(meme_vault), Some(meme_mint), Some(user_meme), Some(meme_token_program)) => {
+                (meme_vault, meme_mint, user_meme, meme_token_program)
+            }
+            _ => {
+                return Err(error!(err::acc(
+                    "Meme accounts and token program are required to buy from a liquid pool"
+                )))
+            }
+        };
+
+        let cpi_accounts = TransferChecked {
+            from: meme_vault.to_account_info(),
+            mint: meme_mint.to_account_info(),
+            to: user_meme.to_account_info(),
+            authority: self.pool_signer_pda.to_account_info(),
+        };
+
+        let cpi_program = meme_token_program.to_account_info();
+        Ok(CpiContext::new(cpi_program, cpi_accounts))
+    }
+
+    /// Helper function to create CPI context for wrapping the user's native SOL
+    fn wrap_user_sol(
+        &self,
+        to: AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, system_program::Transfer<'info>> {
+        let cpi_accounts = system_program::Transfer {
+            from: self.owner.to_account_info(),
+            to,
+        };
+
+        let cpi_program = self.system_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Helper function to create CPI context for syncing the wrapped SOL balance
+    fn sync_user_sol(
+        &self,
+        account: AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, SyncNative<'info>> {
+        let cpi_accounts = SyncNative {
+            account,
+        };
+
+        let cpi_program = self.quote_token_program.to_account_info();
         CpiContext::new(cpi_program, cpi_accounts)
     }
 
@@ -109,79 +232,178 @@ impl<'info> SwapCoinY<'info> {
         let cpi_program = self.token_program.to_account_info();
         CpiContext::new(cpi_program, cpi_accounts)
     }
+
+    /// Helper function to create CPI context for burning the user's points
+    fn burn_user_points(&self) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: self.points_mint.to_account_info(),
+            from: self.user_points.to_account_info(),
+            authority: self.owner.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
 }
 
 /// Handler function for swapping SOL for meme tokens
 /// 
 /// # Arguments
 /// * `ctx` - The context containing all required accounts
-/// * `coin_in_amount` - Amount of SOL to swap
+/// * `coin_in_amount` - Amount of SOL to swap, including any transfer fee of
+///   the quote mint
 /// * `coin_x_min_value` - Minimum amount of meme tokens to receive
 /// * `_ticket_number` - Ticket number for the meme ticket PDA
+/// * `points_to_burn` - Maximum amount of points to burn for a quote fee discount
+/// * `native_sol` - Pay with native SOL, wrapped by the instruction into
+///   `wsol_ata` if passed, which is created if it doesn't exist, or otherwise
+///   into `user_sol`, which must then be a WSOL account of the signer
+/// * `max_avg_price` - Highest average price to pay, in quote tokens per whole
+///   meme token scaled by `PRICE_PRECISION`
+/// * `max_price_impact_bps` - Highest spot price increase caused by the buy
+/// * `deadline_ts` - Unix timestamp after which the buy fails with `Expired`
+///
+/// With a price bound the buy is partially filled up to the bound instead of
+/// failing, only the filled part is paid. Likewise a buy completing the curve
+/// only pays for what's left of it, fee included.
+///
+/// The meme tokens bought go into a new meme ticket, or straight into
+/// `user_meme` if the pool is liquid. `coin_x_min_value` then applies to what
+/// reaches `user_meme` after any transfer fee of the meme mint.
 pub fn handle(
     ctx: Context<SwapCoinY>,
     coin_in_amount: u64,
     coin_x_min_value: u64,
     _ticket_number: u64,
+    points_to_burn: u64,
+    native_sol: bool,
+    max_avg_price: Option<u128>,
+    max_price_impact_bps: Option<u64>,
+    deadline_ts: Option<i64>,
 ) -> Result<()> {
     /// Get accounts from context
     let accs = ctx.accounts;
 
-    /// Check that input amount is not zero
-    if coin_in_amount == 0 {
-        return Err(error!(AmmError::NoZeroTokens));
+    /// Don't execute at a stale price
+    check_deadline(deadline_ts, Clock::get()?.unix_timestamp)?;
+
+    /// Liquid pools pay out tokens, all other pools tickets
+    if accs.pool.liquid {
+        if accs.meme_ticket.is_some() {
+            return Err(error!(err::acc("Liquid pools don't issue meme tickets")));
+        }
+
+        if accs.meme_vault.is_none()
+            || accs.meme_mint.is_none()
+            || accs.user_meme.is_none()
+            || accs.meme_token_program.is_none()
+        {
+            return Err(error!(err::acc(
+                "Meme accounts and token program are required to buy from a liquid pool"
+            )));
+        }
+    } else if accs.meme_ticket.is_none() {
+        return Err(error!(err::acc("Meme ticket is required to buy")));
     }
 
-    /// Check that pool is not locked
-    if accs.pool.locked {
-        return Err(error!(AmmError::PoolIsLocked));
+    /// Native SOL can only be wrapped into the signer's own WSOL account
+    if native_sol {
+        if accs.pool.quote_reserve.mint != spl_token::native_mint::ID {
+            return Err(error!(AmmError::QuoteMintNotNative));
+        }
+
+        if accs.wsol_ata.is_none() && accs.user_sol.owner != accs.owner.key() {
+            return Err(error!(err::acc("Only the signer's own WSOL account can be wrapped into")));
+        }
+    } else if accs.wsol_ata.is_some() {
+        return Err(error!(err::acc("WSOL account is only used to pay with native SOL")));
     }
 
-    /// Calculate swap amounts
+    /// Resolve the fees after the points-burn discount, if any
+    let (fees, discount_tier) = discounted_fees(
+        accs.fee_discount_config.as_deref(),
+        &accs.pool.fees,
+        points_to_burn,
+    )?;
+
+    /// Only what reaches the vault after the quote mint's transfer fee is swapped
+    let quote_mint_info = accs.quote_mint.to_account_info();
+    let transfer_fee = token_ext::transfer_fee(&quote_mint_info, coin_in_amount)?;
+
+    /// Price bounds the buy is partially filled within
+    let limit = PriceLimit {
+        avg_price: max_avg_price,
+        max_price_impact_bps,
+    };
+
+    /// Run the buy against the curve and update the pool
     let swap_amount = accs
         .pool
-        .swap_amounts(coin_in_amount, coin_x_min_value, true);
+        .execute_buy(
+            coin_in_amount - transfer_fee,
+            coin_x_min_value,
+            &fees,
+            &limit,
+            Clock::get()?.slot,
+        )?;
 
-    /// Transfer SOL from user to pool
-    token::transfer(
-        accs.send_user_tokens(),
+    /// Burn the points paid for the discount
+    if let Some(tier) = discount_tier {
+        token::burn(accs.burn_user_points(), tier.points_amount)?;
+
+        msg!(
+            "points_burned: {}\n fee_discount: {}",
+            tier.points_amount,
+            tier.discount
+        );
+    }
+
+    /// Amount the user pays, grossed up so the vault receives the full amount
+    let amount_to_send = token_ext::amount_with_transfer_fee(
+        &quote_mint_info,
         swap_amount.amount_in + swap_amount.admin_fee_in,
-    )
-    .unwrap();
+    )?;
+
+    /// Wrap exactly the amount being paid
+    if native_sol {
+        let wsol = accs.quote_source(true);
+        system_program::transfer(accs.wrap_user_sol(wsol.clone()), amount_to_send)?;
+        token_interface::sync_native(accs.sync_user_sol(wsol))?;
+    }
+
+    /// Transfer SOL from user to pool
+    token_interface::transfer_checked(
+        accs.send_user_tokens(accs.quote_source(native_sol)),
+        amount_to_send,
+        accs.quote_mint.decimals,
+    )?;
+
+    /// Make sure the vault received everything the pool accounted for
+    accs.quote_vault.reload()?;
+    accs.pool.check_vault_invariant(accs.quote_vault.amount)?;
 
     /// Create points PDA signer seeds
     let point_pda: &[&[u8]] = &[POINTS_PDA, &[ctx.bumps.points_pda]];
     let point_pda_seeds = &[&point_pda[..]];
 
-    /// Get available points amount
-    let available_points_amt = accs.points_acc.amount;
-
     /// Calculate points for swap
     let points = get_swap_points(
         swap_amount.amount_in + swap_amount.admin_fee_in,
         &accs.points_epoch,
-    );
+    )?;
     /// Clamp points to available amount
-    let clamped_points = min(available_points_amt, points);
-    
+    let (clamped_points, clamped_referrer_points) =
+        clamp_swap_points(points, accs.points_acc.amount)?;
+
     /// Transfer points if available
     if clamped_points > 0 {
         token::transfer(
             accs.send_user_points().with_signer(point_pda_seeds),
             clamped_points,
-        )
-        .unwrap();
+        )?;
 
         /// Handle referral points if referrer provided
         if let Some(referrer) = &mut accs.referrer_points {
-            let available_points_amt = if available_points_amt > clamped_points {
-                available_points_amt - clamped_points
-            } else {
-                0
-            };
-            let referrer_points = clamped_points.mul_div_floor(25_000, 100_000).unwrap();
-            let clamped_referrer_points = min(available_points_amt, referrer_points);
-
             if clamped_referrer_points > 0 {
                 let cpi_accounts = Transfer {
                     from: accs.points_acc.to_account_info(),
@@ -194,36 +416,55 @@ pub fn handle(
                 token::transfer(
                     CpiContext::new(cpi_program, cpi_accounts).with_signer(point_pda_seeds),
                     clamped_referrer_points,
-                )
-                .unwrap();
+                )?;
             }
         }
     }
 
-    /// Get mutable reference to pool
-    let pool = &mut accs.pool;
+    /// Get swap output amount
+    let swap_amount_out = swap_amount.amount_out;
 
-    /// Update pool admin fees
-    pool.admin_fees_quote += swap_amount.admin_fee_in;
-    pool.admin_fees_meme += swap_amount.admin_fee_out;
+    if accs.pool.liquid {
+        /// Send the meme tokens straight to the user
+        let meme_mint = accs
+            .meme_mint
+            .as_ref()
+            .ok_or_else(|| err::acc("Meme mint is required to buy from a liquid pool"))?;
+        let meme_decimals = meme_mint.decimals;
+        let meme_transfer_fee =
+            token_ext::transfer_fee(&meme_mint.to_account_info(), swap_amount_out)?;
 
-    /// Update pool reserves
-    pool.quote_reserve.tokens += swap_amount.amount_in;
-    pool.meme_reserve.tokens -= swap_amount.amount_out + swap_amount.admin_fee_out;
+        if swap_amount_out - meme_transfer_fee < coin_x_min_value {
+            return Err(error!(AmmError::SlippageExceeded));
+        }
 
-    /// Lock pool if meme tokens depleted
-    if pool.meme_reserve.tokens == 0 {
-        pool.locked = true;
-    };
+        let seeds = &[
+            BoundPool::SIGNER_PDA_PREFIX,
+            &accs.pool.key().to_bytes()[..],
+            &[ctx.bumps.pool_signer_pda],
+        ];
 
-    /// Get swap output amount
-    let swap_amount_out = swap_amount.amount_out;
+        let signer_seeds = &[&seeds[..]];
 
-    /// Get mutable reference to meme ticket
-    let meme_ticket = &mut accs.meme_ticket;
+        token_interface::transfer_checked(
+            accs.send_meme_to_user()?.with_signer(signer_seeds),
+            swap_amount_out,
+            meme_decimals,
+        )?;
+    } else {
+        /// Get mutable reference to meme ticket
+        let pool_key = accs.pool.key();
+        let meme_ticket = accs
+            .meme_ticket
+            .as_mut()
+            .ok_or_else(|| err::acc("Meme ticket is required to buy"))?;
 
-    /// Initialize meme ticket
-    meme_ticket.setup(pool.key(), accs.owner.key(), swap_amount_out);
+        /// Initialize meme ticket
+        meme_ticket.setup(pool_key, accs.owner.key(), swap_amount_out)?;
+
+        /// The ticket's tokens stay in the meme vault
+        accs.pool.hold_ticket_tokens(swap_amount_out)?;
+    }
 
     /// Log swap amounts
     msg!(
@@ -240,11 +481,34 @@ pub fn handle(
 /// # Arguments
 /// * `buy_amount` - Amount of SOL being swapped
 /// * `points_epoch` - Current points epoch with points rate
-pub fn get_swap_points(buy_amount: u64, points_epoch: &PointsEpoch) -> u64 {
-    return buy_amount
+pub fn get_swap_points(buy_amount: u64, points_epoch: &PointsEpoch) -> Result<u64> {
+    if points_epoch.points_per_sol_denom == 0 {
+        return Err(error!(AmmError::DivideByZero));
+    }
+
+    return Ok(buy_amount
         .mul_div_floor(
             points_epoch.points_per_sol_num,
             points_epoch.points_per_sol_denom,
         )
-        .unwrap();
+        .ok_or(AmmError::MulDivOverflow)?);
+}
+
+/// Clamp the points earned for a buy to the points still available
+///
+/// Returns the points for the buyer and the referrer's share, the latter
+/// clamped to whatever remains available after paying the buyer.
+///
+/// # Arguments
+/// * `points` - Points earned for the buy
+/// * `available_points_amt` - Points left to distribute
+pub fn clamp_swap_points(points: u64, available_points_amt: u64) -> Result<(u64, u64)> {
+    let clamped_points = min(available_points_amt, points);
+    let remaining_points_amt = available_points_amt - clamped_points;
+
+    let referrer_points = clamped_points
+        .mul_div_floor(25_000, 100_000)
+        .ok_or(AmmError::MulDivOverflow)?;
+
+    Ok((clamped_points, min(remaining_points_amt, referrer_points)))
 }
@@ -42,9 +42,13 @@ pub enum AmmError {
     NonZeroAmountTicket,
     #[msg("Can't unstake the required amount of tokens")]
     NotEnoughTokensToRelease,
+    #[msg("Price factored LP supply must be below the trading supply")]
     BondingCurveMustBeNegativelySloped,
+    #[msg("Price factored LP supply must be below twice the trading supply")]
     BondingCurveInterceptMustBePositive,
+    #[msg("Quote target is too high for the trading supply")]
     EGammaSAboveRelativeLimit,
+    #[msg("Trading supply is too small for the quote target to price it precisely")]
     EScaleTooLow,
     InvalidAmmAccountOwner,
     ExpectedAccount,
@@ -56,6 +60,32 @@ pub enum AmmError {
     NonZeroInitialMemeSupply,
     AirdroppedTokensOvercap,
     InvalidVestingPeriod,
+    #[msg("Fee discount tiers must grow in both points and discount")]
+    InvalidFeeDiscountSchedule,
+    #[msg("Points can't be burned for a discount without the fee discount config")]
+    FeeDiscountUnavailable,
+    #[msg("Mint has a token extension pools don't support")]
+    UnsupportedMintExtension,
+    #[msg("Native SOL can only be swapped on pools quoted in wrapped SOL")]
+    QuoteMintNotNative,
+    #[msg("Meme mint authority was already revoked")]
+    MemeSupplyAlreadyFixed,
+    #[msg("Dev-buy exceeds the share of trading tokens creators can buy")]
+    DevBuyOvercap,
+    #[msg("Pool has no tokens reserved for an airdrop")]
+    NoAirdropTokens,
+    #[msg("Merkle proof doesn't match the airdrop root")]
+    InvalidAirdropProof,
+    #[msg("Airdrop expired or was clawed back")]
+    AirdropExpired,
+    #[msg("Airdrop can only be clawed back after its expiry")]
+    AirdropNotExpired,
+    #[msg("No part of the swap is within the price limit")]
+    PriceLimitExceeded,
+    #[msg("Transaction landed after its deadline")]
+    Expired,
+    #[msg("Airdropped tokens can only be withdrawn through vesting")]
+    AirdropTicketNotSellable,
 }
 
 #[allow(dead_code)]
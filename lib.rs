@@ -1,10 +1,16 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
-use crate::errors::AmmError;
-use crate::models::bound::BoundPool;
+use crate::err::AmmError;
+use crate::models::board::{
+    Board, PropertyStake, PropertyState, StakerPosition, MONOPOLY_POINTS_MULTIPLIER,
+};
+use crate::models::bound::{check_deadline, BoundPool, PriceLimit};
 use crate::models::points_epoch::PointsEpoch;
-use crate::consts::{POINTS_MINT, POINTS_PDA};
-use std::cmp::min;
+use crate::consts::{
+    POINTS_MINT, POINTS_PDA, PROPERTY_RENT_FEE_PERCENTAGE_DENOM, PROPERTY_RENT_FEE_PERCENTAGE_NUM,
+};
+use crate::endpoints::swap_y::{clamp_swap_points, get_swap_points};
+use crate::libraries::MulDiv;
 
 // Error codes for the Monopoly game system
 #[error_code]
@@ -23,19 +29,51 @@ pub enum MonopolyError {
     
     #[msg("Points distribution failed")]
     PointsDistributionFailed,
+
+    #[msg("Tile at this position can't be staked into")]
+    PropertyNotStakeable,
+
+    #[msg("A roll is already committed and must be revealed first")]
+    RollAlreadyCommitted,
+
+    #[msg("No roll was committed")]
+    NoRollCommitted,
+
+    #[msg("Revealed secret doesn't match the committed roll")]
+    InvalidReveal,
+
+    #[msg("Roll can only be revealed in a slot after the commitment")]
+    RevealTooEarly,
+
+    #[msg("Roll wasn't revealed in time and has to be committed again")]
+    RevealExpired,
+
+    #[msg("Effect of the previous landing must be resolved first")]
+    LandingNotResolved,
+
+    #[msg("There's no landing to resolve")]
+    NoPendingLanding,
+
+    #[msg("Not enough time passed since the stake was purchased")]
+    PropertyStakeLocked,
 }
 
 // Account validation struct with all required accounts for property stake purchase
 #[derive(Accounts)]
-#[instruction(position: u8, ticket_number: u64)]
+#[instruction(position: u8, coin_in_amount: u64, coin_x_min_value: u64, ticket_number: u64)]
 pub struct BuyPropertyStake<'info> {
+    // Board the property belongs to
+    #[account(mut, has_one = pool)]
+    pub board: Account<'info, Board>,
+
     // Property stake account - stores individual stake information
     #[account(
         init,
         payer = owner,
         space = 8 + PropertyStake::INIT_SPACE,
         seeds = [
-            b"property_stake",
+            PropertyStake::PROPERTY_STAKE_PREFIX,
+            board.key().as_ref(),
             position.to_le_bytes().as_ref(),
             owner.key().as_ref(),
             ticket_number.to_le_bytes().as_ref()
@@ -44,16 +82,37 @@ pub struct BuyPropertyStake<'info> {
     )]
     pub property_stake: Account<'info, PropertyStake>,
 
-    // Property state account - stores aggregate property information
+    // Property state account - stores aggregate property information, shared
+    // by every staker of the position
     #[account(
-        init,
+        init_if_needed,
         payer = owner,
         space = 8 + PropertyState::INIT_SPACE,
-        seeds = [b"property_state", position.to_le_bytes().as_ref()],
+        seeds = [
+            PropertyState::PROPERTY_STATE_PREFIX,
+            board.key().as_ref(),
+            position.to_le_bytes().as_ref()
+        ],
         bump
     )]
     pub property_state: Account<'info, PropertyState>,
 
+    // Staker position account - total stake of the user in the property,
+    // which decides the ownership
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + StakerPosition::INIT_SPACE,
+        seeds = [
+            StakerPosition::STAKER_POSITION_PREFIX,
+            board.key().as_ref(),
+            position.to_le_bytes().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump
+    )]
+    pub staker_position: Account<'info, StakerPosition>,
+
     // Bonding curve pool account
     #[account(mut)]
     pub pool: Account<'info, BoundPool>,
@@ -69,6 +128,13 @@ pub struct BuyPropertyStake<'info> {
     #[account(mut)]
     pub user_sol: Account<'info, TokenAccount>,
 
+    // Board's rent vault, receives the rent paid to the property's stakers
+    #[account(
+        mut,
+        constraint = board.rent_vault == rent_vault.key()
+    )]
+    pub rent_vault: Account<'info, TokenAccount>,
+
     // User's points account
     #[account(
         mut,
@@ -126,29 +192,44 @@ pub fn buy_property_stake(
     coin_in_amount: u64,
     coin_x_min_value: u64,
     ticket_number: u64,
+    deadline_ts: Option<i64>,
 ) -> Result<()> {
-    // Step 1: Validate basic parameters
-    require!(position < 40, MonopolyError::InvalidPropertyPosition); // Monopoly board has 40 spaces
-    require!(coin_in_amount > 0, AmmError::NoZeroTokens);
-    require!(!ctx.accounts.pool.locked, AmmError::PoolIsLocked);
+    // Step 1: Validate basic parameters, a late transaction mustn't buy at a
+    // stale price
+    check_deadline(deadline_ts, Clock::get()?.unix_timestamp)?;
 
-    
-    
-    let swap_amount = swap_calc_accounts
-        .pool
-        .swap_amounts(coin_in_amount, coin_x_min_value, true);
+    let tile = *ctx.accounts.board.tile(position)?;
+    require!(tile.is_stakeable(), MonopolyError::PropertyNotStakeable);
 
-    // Validate minimum output amount
-    require!(
-        swap_amount.amount_out >= coin_x_min_value,
-        MonopolyError::InsufficientOutputAmount
-    );
+    // Step 2: Run the buy against the curve through the shared buy path,
+    // which also updates the pool's reserves and fees
+    let fees = ctx.accounts.pool.fees;
+    let swap_amount = ctx
+        .accounts
+        .pool
+        .execute_buy(
+            coin_in_amount,
+            coin_x_min_value,
+            &fees,
+            &PriceLimit::default(),
+            Clock::get()?.slot,
+        )?;
 
     // Step 3: Record current timestamp
     let clock = Clock::get()?;
     let current_timestamp = clock.unix_timestamp;
 
-    // Step 4: Execute SOL transfer
+    // Step 4: Execute SOL transfer, the property's existing stakers are paid
+    // rent on top of the fee, so that it never comes out of the protocol fee
+    let rent = if ctx.accounts.property_state.total_investment > 0 {
+        swap_amount
+            .admin_fee_in
+            .mul_div_floor(PROPERTY_RENT_FEE_PERCENTAGE_NUM, PROPERTY_RENT_FEE_PERCENTAGE_DENOM)
+            .ok_or(AmmError::MulDivOverflow)?
+    } else {
+        0
+    };
+
     token::transfer(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
@@ -161,16 +242,60 @@ pub fn buy_property_stake(
         swap_amount.amount_in + swap_amount.admin_fee_in,
     )?;
 
-    // Step 5: Handle points distribution
+    if rent > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_sol.to_account_info(),
+                    to: ctx.accounts.rent_vault.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            rent,
+        )?;
+    }
+
+    // Step 5: Claim the property if the user's total stake is large enough,
+    // owning the whole set multiplies the points earned
+    let owner_key = ctx.accounts.owner.key();
+    let staker_position = &mut ctx.accounts.staker_position;
+    if staker_position.board == Pubkey::default() {
+        staker_position.board = ctx.accounts.board.key();
+        staker_position.owner = owner_key;
+        staker_position.property_position = position;
+    }
+    staker_position.total_stake = staker_position
+        .total_stake
+        .checked_add(swap_amount.amount_out)
+        .ok_or(AmmError::MathOverflow)?;
+
+    if ctx
+        .accounts
+        .property_state
+        .try_claim_ownership(&tile, &ctx.accounts.staker_position)?
+    {
+        ctx.accounts.board.owners[position as usize] = owner_key;
+    }
+
+    let points_multiplier = if ctx.accounts.board.owns_group(&owner_key, tile.group) {
+        MONOPOLY_POINTS_MULTIPLIER
+    } else {
+        1
+    };
+
+    // Step 6: Handle points distribution
     let point_pda: &[&[u8]] = &[POINTS_PDA, &[ctx.bumps.points_pda]];
     let point_pda_seeds = &[&point_pda[..]];
 
-    let available_points = ctx.accounts.points_acc.amount;
     let points = get_swap_points(
         swap_amount.amount_in + swap_amount.admin_fee_in,
         &ctx.accounts.points_epoch
-    );
-    let clamped_points = min(available_points, points);
+    )?
+    .checked_mul(points_multiplier)
+    .ok_or(AmmError::MathOverflow)?;
+    let (clamped_points, referral_points) =
+        clamp_swap_points(points, ctx.accounts.points_acc.amount)?;
 
     if clamped_points > 0 {
         // Transfer points to user
@@ -189,7 +314,6 @@ pub fn buy_property_stake(
 
         // Handle referral points if provided
         if let Some(referrer) = &ctx.accounts.referrer_points {
-            let referral_points = clamped_points.mul_div_floor(25_000, 100_000)?;
             if referral_points > 0 {
                 token::transfer(
                     CpiContext::new_with_signer(
@@ -207,36 +331,51 @@ pub fn buy_property_stake(
         }
     }
 
-    // Step 6: Update pool state
+    // Step 7: Make sure the vault received everything the pool accounted for
     let pool = &mut ctx.accounts.pool;
-    pool.admin_fees_quote += swap_amount.admin_fee_in;
-    pool.admin_fees_meme += swap_amount.admin_fee_out;
-    pool.quote_reserve.tokens += swap_amount.amount_in;
-    pool.meme_reserve.tokens -= swap_amount.amount_out + swap_amount.admin_fee_out;
 
-    if pool.meme_reserve.tokens == 0 {
-        pool.locked = true;
-    }
+    ctx.accounts.quote_vault.reload()?;
+    pool.check_vault_invariant(ctx.accounts.quote_vault.amount)?;
 
-    // Step 7: Create property stake record
+    // Step 8: Create property stake record
     let property_stake = &mut ctx.accounts.property_stake;
+    property_stake.board = ctx.accounts.board.key();
     property_stake.property_position = position;
     property_stake.owner = ctx.accounts.owner.key();
     property_stake.stake_amount = swap_amount.amount_out;
     property_stake.purchase_timestamp = current_timestamp;
 
-    // Step 8: Update property state
+    // The stake's tokens stay in the meme vault
+    pool.hold_ticket_tokens(swap_amount.amount_out)?;
+
+    // Step 9: Update property state, initializing it for the first staker
     let property_state = &mut ctx.accounts.property_state;
-    property_state.total_investment += swap_amount.amount_out;
-    property_state.stake_count += 1;
+    if property_state.board == Pubkey::default() {
+        property_state.board = ctx.accounts.board.key();
+        property_state.pool = pool.key();
+        property_state.position = position;
+    }
+    property_state.distribute_rent(rent)?;
+    property_state.total_investment = property_state
+        .total_investment
+        .checked_add(swap_amount.amount_out)
+        .ok_or(AmmError::MathOverflow)?;
+    property_state.stake_count = property_state
+        .stake_count
+        .checked_add(1)
+        .ok_or(AmmError::MathOverflow)?;
+
+    // New stakes only earn rent paid from now on
+    property_stake.checkpoint_rent(property_state)?;
 
     // Log transaction details
     msg!(
-        "Property stake created - Position: {}, SOL invested: {}, Memecoin received: {}, Points earned: {}",
+        "Property stake created - Position: {}, SOL invested: {}, Memecoin received: {}, Points earned: {}, Rent paid: {}",
         position,
         coin_in_amount,
         swap_amount.amount_out,
-        clamped_points
+        clamped_points,
+        rent
     );
 
     Ok(())
@@ -11,7 +11,7 @@ use crate::libraries::big_num::{U128, U256};
 /// Implementations of this trait have to ensure that even if the result of the multiplication does
 /// not fit into the type, as long as it would fit after the division the correct result has to be
 /// returned instead of `None`. `None` only should be returned if the overall result does not fit
-/// into the type.
+/// into the type, or if `denom` is zero.
 ///
 /// This specifically means that e.g. the `u64` implementation must, depending on the arguments, be
 /// able to do 128 bit integer multiplication.
@@ -107,7 +107,9 @@ impl MulDiv for u64 {
     type Output = u64;
 
     fn mul_div_floor(self, num: Self, denom: Self) -> Option<Self::Output> {
-        assert_ne!(denom, 0);
+        if denom == 0 {
+            return None;
+        }
         let r = (U128::from(self) * U128::from(num)) / U128::from(denom);
         if r > U128::from(u64::MAX) {
             None
@@ -117,7 +119,9 @@ impl MulDiv for u64 {
     }
 
     fn mul_div_ceil(self, num: Self, denom: Self) -> Option<Self::Output> {
-        assert_ne!(denom, 0);
+        if denom == 0 {
+            return None;
+        }
         let r = (U128::from(self) * U128::from(num) + U128::from(denom - 1)) / U128::from(denom);
         if r > U128::from(u64::MAX) {
             None
@@ -135,7 +139,9 @@ impl MulDiv for U128 {
     type Output = U128;
 
     fn mul_div_floor(self, num: Self, denom: Self) -> Option<Self::Output> {
-        assert_ne!(denom, U128::default());
+        if denom == U128::default() {
+            return None;
+        }
         let r = ((self.as_u256()) * (num.as_u256())) / (denom.as_u256());
         if r > U128::MAX.as_u256() {
             None
@@ -145,7 +151,9 @@ impl MulDiv for U128 {
     }
 
     fn mul_div_ceil(self, num: Self, denom: Self) -> Option<Self::Output> {
-        assert_ne!(denom, U128::default());
+        if denom == U128::default() {
+            return None;
+        }
         let r = (self.as_u256() * num.as_u256() + (denom - 1).as_u256()) / denom.as_u256();
         if r > U128::MAX.as_u256() {
             None
@@ -167,7 +175,9 @@ impl MulDiv for U256 {
     type Output = U256;
 
     fn mul_div_floor(self, num: Self, denom: Self) -> Option<Self::Output> {
-        assert_ne!(denom, U256::default());
+        if denom == U256::default() {
+            return None;
+        }
         let r = (self * num) / denom;
         if r > U128::MAX.as_u256() {
             None
@@ -177,7 +187,9 @@ impl MulDiv for U256 {
     }
 
     fn mul_div_ceil(self, num: Self, denom: Self) -> Option<Self::Output> {
-        assert_ne!(denom, U256::default());
+        if denom == U256::default() {
+            return None;
+        }
         let r = (self * num + (denom - 1)) / denom;
         if r > U128::MAX.as_u256() {
             None
@@ -194,3 +206,37 @@ impl MulDiv for U256 {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mul_div_zero_denom() {
+        assert_eq!(7u64.mul_div_floor(3, 0), None);
+        assert_eq!(7u64.mul_div_ceil(3, 0), None);
+
+        assert_eq!(U128::from(7u64).mul_div_floor(U128::from(3u64), U128::default()), None);
+        assert_eq!(U128::from(7u64).mul_div_ceil(U128::from(3u64), U128::default()), None);
+
+        assert_eq!(U256::from(7u64).mul_div_floor(U256::from(3u64), U256::default()), None);
+        assert_eq!(U256::from(7u64).mul_div_ceil(U256::from(3u64), U256::default()), None);
+    }
+
+    #[test]
+    fn test_mul_div_rounding() {
+        assert_eq!(7u64.mul_div_floor(3, 2), Some(10));
+        assert_eq!(7u64.mul_div_ceil(3, 2), Some(11));
+        assert_eq!(u64::MAX.mul_div_floor(u64::MAX, u64::MAX), Some(u64::MAX));
+        assert_eq!(u64::MAX.mul_div_floor(2, 1), None);
+
+        assert_eq!(
+            U128::from(7u64).mul_div_ceil(U128::from(3u64), U128::from(2u64)),
+            Some(U128::from(11u64))
+        );
+        assert_eq!(
+            U256::from(7u64).mul_div_floor(U256::from(3u64), U256::from(2u64)),
+            Some(U256::from(10u64))
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use solana_program::keccak::hashv;
+
+/// Merkle distributor of the meme tokens a pool reserved for its airdrop.
+///
+/// Each leaf is `keccak(index, claimant, amount)` and proofs hash sorted
+/// pairs, the layout of the common Merkle distributor tooling.
+#[account]
+#[derive(Default)]
+pub struct AirdropDistributor {
+    pub pool: Pubkey,
+    pub merkle_root: [u8; 32],
+    /// Tokens the tree distributes, i.e. the pool's airdropped tokens
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub num_claimed: u64,
+    /// Unix timestamp after which unclaimed tokens can be clawed back
+    pub expiry_ts: i64,
+    pub clawed_back: bool,
+    pub padding: [u8; 32],
+}
+
+impl AirdropDistributor {
+    pub const AIRDROP_PREFIX: &'static [u8; 7] = b"airdrop";
+
+    pub fn space() -> usize {
+        let discriminant = 8;
+        let pool = 32;
+        let merkle_root = 32;
+        let total_amount = 8;
+        let claimed_amount = 8;
+        let num_claimed = 8;
+        let expiry_ts = 8;
+        let clawed_back = 1;
+        let padding = 32;
+
+        discriminant
+            + pool
+            + merkle_root
+            + total_amount
+            + claimed_amount
+            + num_claimed
+            + expiry_ts
+            + clawed_back
+            + padding
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expiry_ts
+    }
+
+    pub fn unclaimed_amount(&self) -> u64 {
+        self.total_amount - self.claimed_amount
+    }
+
+    pub fn leaf(index: u64, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+        hashv(&[
+            &index.to_le_bytes(),
+            claimant.as_ref(),
+            &amount.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    pub fn verify(&self, proof: &[[u8; 32]], leaf: [u8; 32]) -> bool {
+        let mut computed = leaf;
+
+        for node in proof {
+            computed = if computed <= *node {
+                hashv(&[&computed, node]).to_bytes()
+            } else {
+                hashv(&[node, &computed]).to_bytes()
+            };
+        }
+
+        computed == self.merkle_root
+    }
+}
+
+/// Marks a leaf of an [`AirdropDistributor`] as claimed.
+#[account]
+#[derive(Default)]
+pub struct ClaimStatus {
+    pub distributor: Pubkey,
+    pub claimant: Pubkey,
+    pub amount: u64,
+    pub claimed_at: i64,
+}
+
+impl ClaimStatus {
+    pub const CLAIM_STATUS_PREFIX: &'static [u8; 12] = b"claim_status";
+
+    pub fn space() -> usize {
+        let discriminant = 8;
+        let distributor = 32;
+        let claimant = 32;
+        let amount = 8;
+        let claimed_at = 8;
+
+        discriminant + distributor + claimant + amount + claimed_at
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        if a <= b {
+            hashv(&[a, b]).to_bytes()
+        } else {
+            hashv(&[b, a]).to_bytes()
+        }
+    }
+
+    /// Four leaf tree, returns the distributor and the proof of every leaf.
+    fn tree(claims: &[(Pubkey, u64); 4]) -> (AirdropDistributor, Vec<Vec<[u8; 32]>>) {
+        let leaves: Vec<[u8; 32]> = claims
+            .iter()
+            .enumerate()
+            .map(|(i, (claimant, amount))| AirdropDistributor::leaf(i as u64, claimant, *amount))
+            .collect();
+
+        let left = hash_pair(&leaves[0], &leaves[1]);
+        let right = hash_pair(&leaves[2], &leaves[3]);
+
+        let distributor = AirdropDistributor {
+            merkle_root: hash_pair(&left, &right),
+            ..Default::default()
+        };
+        let proofs = vec![
+            vec![leaves[1], right],
+            vec![leaves[0], right],
+            vec![leaves[3], left],
+            vec![leaves[2], left],
+        ];
+
+        (distributor, proofs)
+    }
+
+    #[test]
+    fn test_leaf_layout() {
+        let claimant = Pubkey::new_from_array([1; 32]);
+
+        let expected = hashv(&[&7u64.to_le_bytes(), &[1; 32], &500u64.to_le_bytes()]).to_bytes();
+
+        assert_eq!(AirdropDistributor::leaf(7, &claimant, 500), expected);
+        assert_ne!(AirdropDistributor::leaf(7, &claimant, 501), expected);
+        assert_ne!(AirdropDistributor::leaf(8, &claimant, 500), expected);
+    }
+
+    #[test]
+    fn test_verify_known_proofs() {
+        let claims = [
+            (Pubkey::new_unique(), 100),
+            (Pubkey::new_unique(), 200),
+            (Pubkey::new_unique(), 300),
+            (Pubkey::new_unique(), 400),
+        ];
+        let (distributor, proofs) = tree(&claims);
+
+        for (i, (claimant, amount)) in claims.iter().enumerate() {
+            let leaf = AirdropDistributor::leaf(i as u64, claimant, *amount);
+            assert!(distributor.verify(&proofs[i], leaf));
+        }
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_proofs() {
+        let claims = [
+            (Pubkey::new_unique(), 100),
+            (Pubkey::new_unique(), 200),
+            (Pubkey::new_unique(), 300),
+            (Pubkey::new_unique(), 400),
+        ];
+        let (distributor, proofs) = tree(&claims);
+        let (claimant, amount) = claims[0];
+        let leaf = AirdropDistributor::leaf(0, &claimant, amount);
+
+        let mut tampered = proofs[0].clone();
+        tampered[1][0] ^= 1;
+        assert!(!distributor.verify(&tampered, leaf));
+
+        // Another leaf's proof, a larger amount or another index don't verify
+        assert!(!distributor.verify(&proofs[1], leaf));
+        assert!(!distributor.verify(&proofs[0], AirdropDistributor::leaf(0, &claimant, amount + 1)));
+        assert!(!distributor.verify(&proofs[0], AirdropDistributor::leaf(1, &claimant, amount)));
+        assert!(!distributor.verify(&proofs[0][..1], leaf));
+    }
+}
@@ -0,0 +1,597 @@
+use crate::consts::{LOCK_TIME, MEME_TOKEN_DECIMALS};
+use crate::err::AmmError;
+use crate::libraries::MulDiv;
+use crate::models::dice::DiceRoll;
+use crate::MonopolyError;
+use anchor_lang::prelude::*;
+
+pub const BOARD_SIZE: usize = 40;
+pub const JAIL_POSITION: u8 = 10;
+/// Scale of the rent-per-share accumulator.
+pub const RENT_PRECISION: u128 = 1_000_000_000_000;
+/// Board dollars per whole quote token.
+pub const BOARD_DOLLARS_PER_QUOTE: u64 = 10_000;
+pub const LANDING_RENT_PERCENTAGE_NUM: u64 = 10;
+pub const LANDING_RENT_PERCENTAGE_DENOM: u64 = 100;
+/// Raw meme tokens a single stake needs per board dollar of the tile's price
+/// to become its owner.
+pub const OWNERSHIP_TOKENS_PER_DOLLAR: u64 = 10_000 * MEME_TOKEN_DECIMALS;
+/// Points multiplier for purchases into a set fully owned by the buyer.
+pub const MONOPOLY_POINTS_MULTIPLIER: u64 = 2;
+
+/// What happens when a player lands on a tile.
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TileKind {
+    Go,
+    Property,
+    Railroad,
+    Utility,
+    Chance,
+    CommunityChest,
+    Tax,
+    Jail,
+    FreeParking,
+    GoToJail,
+}
+
+/// Set a tile belongs to. Railroads and utilities form their own sets.
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ColorGroup {
+    None,
+    Brown,
+    LightBlue,
+    Pink,
+    Orange,
+    Red,
+    Yellow,
+    Green,
+    DarkBlue,
+    Railroad,
+    Utility,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace, Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Tile {
+    pub kind: TileKind,
+    pub group: ColorGroup,
+    /// Purchase price for stakeable tiles and the amount due for tax tiles,
+    /// in classic board dollars.
+    pub base_price: u64,
+}
+
+impl Tile {
+    const fn new(kind: TileKind, group: ColorGroup, base_price: u64) -> Self {
+        Self {
+            kind,
+            group,
+            base_price,
+        }
+    }
+
+    pub fn is_stakeable(&self) -> bool {
+        matches!(
+            self.kind,
+            TileKind::Property | TileKind::Railroad | TileKind::Utility
+        )
+    }
+
+    /// Amount due by a player landing on this tile, in board dollars.
+    pub fn landing_fee(&self) -> Result<u64> {
+        match self.kind {
+            TileKind::Property | TileKind::Railroad | TileKind::Utility => Ok(self
+                .base_price
+                .mul_div_floor(LANDING_RENT_PERCENTAGE_NUM, LANDING_RENT_PERCENTAGE_DENOM)
+                .ok_or(AmmError::MulDivOverflow)?),
+            TileKind::Tax => Ok(self.base_price),
+            _ => Ok(0),
+        }
+    }
+}
+
+const fn property(group: ColorGroup, base_price: u64) -> Tile {
+    Tile::new(TileKind::Property, group, base_price)
+}
+
+const fn special(kind: TileKind) -> Tile {
+    Tile::new(kind, ColorGroup::None, 0)
+}
+
+const RAILROAD: Tile = Tile::new(TileKind::Railroad, ColorGroup::Railroad, 200);
+const UTILITY: Tile = Tile::new(TileKind::Utility, ColorGroup::Utility, 150);
+
+/// The standard 40 tile layout, starting from Go.
+pub const CLASSIC_TILES: [Tile; BOARD_SIZE] = [
+    special(TileKind::Go),
+    property(ColorGroup::Brown, 60),
+    special(TileKind::CommunityChest),
+    property(ColorGroup::Brown, 60),
+    Tile::new(TileKind::Tax, ColorGroup::None, 200),
+    RAILROAD,
+    property(ColorGroup::LightBlue, 100),
+    special(TileKind::Chance),
+    property(ColorGroup::LightBlue, 100),
+    property(ColorGroup::LightBlue, 120),
+    special(TileKind::Jail),
+    property(ColorGroup::Pink, 140),
+    UTILITY,
+    property(ColorGroup::Pink, 140),
+    property(ColorGroup::Pink, 160),
+    RAILROAD,
+    property(ColorGroup::Orange, 180),
+    special(TileKind::CommunityChest),
+    property(ColorGroup::Orange, 180),
+    property(ColorGroup::Orange, 200),
+    special(TileKind::FreeParking),
+    property(ColorGroup::Red, 220),
+    special(TileKind::Chance),
+    property(ColorGroup::Red, 220),
+    property(ColorGroup::Red, 240),
+    RAILROAD,
+    property(ColorGroup::Yellow, 260),
+    property(ColorGroup::Yellow, 260),
+    UTILITY,
+    property(ColorGroup::Yellow, 280),
+    special(TileKind::GoToJail),
+    property(ColorGroup::Green, 300),
+    property(ColorGroup::Green, 300),
+    special(TileKind::CommunityChest),
+    property(ColorGroup::Green, 320),
+    RAILROAD,
+    special(TileKind::Chance),
+    property(ColorGroup::DarkBlue, 350),
+    Tile::new(TileKind::Tax, ColorGroup::None, 100),
+    property(ColorGroup::DarkBlue, 400),
+];
+
+/// A game board played on top of a single bonding curve pool.
+#[account]
+#[derive(InitSpace)]
+pub struct Board {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    /// Quote token vault holding rent owed to property stakers
+    pub rent_vault: Pubkey,
+    pub tiles: [Tile; BOARD_SIZE],
+    /// Owner of every tile, mirrors `PropertyState::owner`
+    pub owners: [Pubkey; BOARD_SIZE],
+}
+
+impl Board {
+    pub const BOARD_PREFIX: &'static [u8; 5] = b"board";
+    pub const SIGNER_PDA_PREFIX: &'static [u8; 12] = b"board_signer";
+
+    pub fn tile(&self, position: u8) -> Result<&Tile> {
+        self.tiles
+            .get(position as usize)
+            .ok_or(error!(MonopolyError::InvalidPropertyPosition))
+    }
+
+    /// Whether `owner` owns every tile of the given set.
+    pub fn owns_group(&self, owner: &Pubkey, group: ColorGroup) -> bool {
+        if group == ColorGroup::None || *owner == Pubkey::default() {
+            return false;
+        }
+
+        self.tiles
+            .iter()
+            .zip(self.owners.iter())
+            .filter(|(tile, _)| tile.group == group)
+            .all(|(_, tile_owner)| tile_owner == owner)
+    }
+
+    /// Highest rent or tax a player can owe for landing on any tile of the
+    /// board, in board dollars.
+    pub fn max_landing_fee(&self) -> Result<u64> {
+        let mut max_fee = 0;
+
+        for tile in self.tiles.iter() {
+            max_fee = max_fee.max(tile.landing_fee()?);
+        }
+
+        Ok(max_fee)
+    }
+
+    /// Converts board dollars into raw quote tokens, where `quote_decimals`
+    /// is the raw amount of one whole quote token.
+    pub fn dollars_to_quote(dollars: u64, quote_decimals: u64) -> Result<u64> {
+        Ok(dollars
+            .mul_div_floor(quote_decimals, BOARD_DOLLARS_PER_QUOTE)
+            .ok_or(AmmError::MulDivOverflow)?)
+    }
+}
+
+/// Position and turn state of a single player on a board.
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerState {
+    pub board: Pubkey,
+    pub owner: Pubkey,
+    pub position: u8,
+    pub in_jail: bool,
+    /// Set after a move until the effect of the landed tile was resolved
+    pub pending_landing: bool,
+    /// Hash of the secret of the next roll, zero when no roll is committed
+    pub commitment: [u8; 32],
+    pub commit_slot: u64,
+    pub turns: u64,
+}
+
+impl PlayerState {
+    pub const PLAYER_STATE_PREFIX: &'static [u8; 12] = b"player_state";
+
+    pub fn has_commitment(&self) -> bool {
+        self.commitment != [0; 32]
+    }
+
+    /// Moves the player by the given roll, applying jail and chance effects.
+    pub fn advance(&mut self, board: &Board, roll: &DiceRoll) -> Result<()> {
+        self.turns += 1;
+
+        // A player in jail sits out one turn unless they roll a double
+        if self.in_jail {
+            self.in_jail = false;
+
+            if !roll.is_double() {
+                return Ok(());
+            }
+        }
+
+        self.move_to((self.position + roll.total()) % BOARD_SIZE as u8);
+
+        let tile = board.tile(self.position)?;
+        if matches!(tile.kind, TileKind::Chance | TileKind::CommunityChest) {
+            // The roll's seed past the dice bytes draws the card
+            match roll.seed[16] % 4 {
+                0 => self.move_to(0),
+                1 => self.go_to_jail(),
+                2 => self.move_to((self.position + BOARD_SIZE as u8 - 3) % BOARD_SIZE as u8),
+                _ => {}
+            }
+        }
+
+        if board.tile(self.position)?.kind == TileKind::GoToJail {
+            self.go_to_jail();
+        }
+
+        Ok(())
+    }
+
+    /// Drops a commitment that wasn't revealed in time, costing the turn and
+    /// sending the player to jail. The caller charges the board's highest
+    /// landing fee on top, so that withholding a bad roll never pays off.
+    pub fn forfeit_roll(&mut self) {
+        self.commitment = [0; 32];
+        self.turns += 1;
+        self.go_to_jail();
+    }
+
+    fn move_to(&mut self, position: u8) {
+        self.position = position;
+        self.pending_landing = true;
+    }
+
+    fn go_to_jail(&mut self) {
+        self.position = JAIL_POSITION;
+        self.in_jail = true;
+        self.pending_landing = false;
+    }
+}
+
+/// Aggregate stake information of a single tile on a board.
+#[account]
+#[derive(InitSpace)]
+pub struct PropertyState {
+    pub board: Pubkey,
+    pub pool: Pubkey,
+    pub position: u8,
+    pub total_investment: u64,
+    pub stake_count: u64,
+    /// Rent accrued per staked meme token, scaled by [`RENT_PRECISION`]
+    pub rent_per_share: u128,
+    /// Total rent ever distributed to the stakers of this property
+    pub total_rent: u64,
+    /// Staker with the largest total stake above the ownership threshold
+    pub owner: Pubkey,
+    /// Total stake of `owner` in this property
+    pub owner_stake: u64,
+}
+
+impl PropertyState {
+    pub const PROPERTY_STATE_PREFIX: &'static [u8; 14] = b"property_state";
+
+    /// Splits `amount` of rent between the current stakers pro rata.
+    ///
+    /// Returns `false` without distributing anything if nobody is staked.
+    pub fn distribute_rent(&mut self, amount: u64) -> Result<bool> {
+        if self.total_investment == 0 || amount == 0 {
+            return Ok(false);
+        }
+
+        let increase = (amount as u128)
+            .checked_mul(RENT_PRECISION)
+            .ok_or(AmmError::MathOverflow)?
+            / self.total_investment as u128;
+
+        self.rent_per_share = self
+            .rent_per_share
+            .checked_add(increase)
+            .ok_or(AmmError::MathOverflow)?;
+        self.total_rent = self
+            .total_rent
+            .checked_add(amount)
+            .ok_or(AmmError::MathOverflow)?;
+
+        Ok(true)
+    }
+
+    fn ownership_threshold(tile: &Tile) -> Result<u64> {
+        Ok(tile
+            .base_price
+            .checked_mul(OWNERSHIP_TOKENS_PER_DOLLAR)
+            .ok_or(AmmError::MathOverflow)?)
+    }
+
+    /// Makes the staker the property owner if their total stake clears the
+    /// tile's ownership threshold and is larger than the current owner's.
+    ///
+    /// Returns whether the ownership changed hands.
+    pub fn try_claim_ownership(&mut self, tile: &Tile, staker: &StakerPosition) -> Result<bool> {
+        if self.owner == staker.owner {
+            self.owner_stake = staker.total_stake;
+            return Ok(false);
+        }
+
+        if staker.total_stake < Self::ownership_threshold(tile)?
+            || staker.total_stake <= self.owner_stake
+        {
+            return Ok(false);
+        }
+
+        self.owner = staker.owner;
+        self.owner_stake = staker.total_stake;
+
+        Ok(true)
+    }
+
+    /// Updates the owner's total after they sold a stake, dropping the
+    /// ownership once it falls below the tile's ownership threshold.
+    ///
+    /// Returns whether the property lost its owner.
+    pub fn release_ownership(&mut self, tile: &Tile, staker: &StakerPosition) -> Result<bool> {
+        if self.owner != staker.owner {
+            return Ok(false);
+        }
+
+        if staker.total_stake >= Self::ownership_threshold(tile)? {
+            self.owner_stake = staker.total_stake;
+            return Ok(false);
+        }
+
+        self.owner = Pubkey::default();
+        self.owner_stake = 0;
+
+        Ok(true)
+    }
+}
+
+/// A single stake of a user into a tile.
+#[account]
+#[derive(InitSpace)]
+pub struct PropertyStake {
+    pub board: Pubkey,
+    pub owner: Pubkey,
+    pub property_position: u8,
+    pub stake_amount: u64,
+    pub purchase_timestamp: i64,
+    /// Rent already accounted for, scaled by [`RENT_PRECISION`]
+    pub rent_debt: u128,
+}
+
+impl PropertyStake {
+    pub const PROPERTY_STAKE_PREFIX: &'static [u8; 14] = b"property_stake";
+
+    /// Stakes can be sold back into the curve once the lock time passed since
+    /// their purchase.
+    pub fn is_unlocked(&self, current_ts: i64) -> bool {
+        self.purchase_timestamp + LOCK_TIME <= current_ts
+    }
+
+    fn accrued_rent(&self, state: &PropertyState) -> Result<u128> {
+        Ok((self.stake_amount as u128)
+            .checked_mul(state.rent_per_share)
+            .ok_or(AmmError::MathOverflow)?)
+    }
+
+    /// Rent earned since the last checkpoint.
+    pub fn pending_rent(&self, state: &PropertyState) -> Result<u64> {
+        let pending = self
+            .accrued_rent(state)?
+            .checked_sub(self.rent_debt)
+            .ok_or(AmmError::InvariantViolation)?
+            / RENT_PRECISION;
+
+        Ok(pending as u64)
+    }
+
+    /// Marks all rent accrued so far as paid out.
+    pub fn checkpoint_rent(&mut self, state: &PropertyState) -> Result<()> {
+        self.rent_debt = self.accrued_rent(state)?;
+
+        Ok(())
+    }
+}
+
+/// Total stake of a single user in a tile, summed over all their stakes.
+#[account]
+#[derive(InitSpace)]
+pub struct StakerPosition {
+    pub board: Pubkey,
+    pub owner: Pubkey,
+    pub property_position: u8,
+    pub total_stake: u64,
+}
+
+impl StakerPosition {
+    pub const STAKER_POSITION_PREFIX: &'static [u8; 15] = b"staker_position";
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn property_state(total_investment: u64) -> PropertyState {
+        PropertyState {
+            board: Pubkey::default(),
+            pool: Pubkey::default(),
+            position: 1,
+            total_investment,
+            stake_count: 0,
+            rent_per_share: 0,
+            total_rent: 0,
+            owner: Pubkey::default(),
+            owner_stake: 0,
+        }
+    }
+
+    fn property_stake(stake_amount: u64) -> PropertyStake {
+        PropertyStake {
+            board: Pubkey::default(),
+            owner: Pubkey::default(),
+            property_position: 1,
+            stake_amount,
+            purchase_timestamp: 0,
+            rent_debt: 0,
+        }
+    }
+
+    fn staker(owner: Pubkey, total_stake: u64) -> StakerPosition {
+        StakerPosition {
+            board: Pubkey::default(),
+            owner,
+            property_position: 1,
+            total_stake,
+        }
+    }
+
+    #[test]
+    fn test_max_landing_fee_covers_every_tile() {
+        let board = Board {
+            pool: Pubkey::default(),
+            creator: Pubkey::default(),
+            rent_vault: Pubkey::default(),
+            tiles: CLASSIC_TILES,
+            owners: [Pubkey::default(); BOARD_SIZE],
+        };
+        let max_fee = board.max_landing_fee().unwrap();
+
+        assert_eq!(max_fee, 200);
+        assert!(CLASSIC_TILES
+            .iter()
+            .all(|tile| tile.landing_fee().unwrap() <= max_fee));
+    }
+
+    #[test]
+    fn test_ownership_follows_total_stake() {
+        let tile = CLASSIC_TILES[1];
+        let threshold = PropertyState::ownership_threshold(&tile).unwrap();
+        let mut state = property_state(0);
+        let mut alice = staker(Pubkey::new_unique(), threshold / 2);
+        let bob = staker(Pubkey::new_unique(), threshold);
+
+        // Neither stake of alice clears the threshold on its own, but both do
+        assert!(!state.try_claim_ownership(&tile, &alice).unwrap());
+        alice.total_stake += threshold / 2 + 1;
+        assert!(state.try_claim_ownership(&tile, &alice).unwrap());
+        assert_eq!(state.owner, alice.owner);
+
+        // A single stake larger than any of alice's doesn't beat her total
+        assert!(!state.try_claim_ownership(&tile, &bob).unwrap());
+        assert_eq!(state.owner, alice.owner);
+
+        // Selling down below bob hands the property over on his next claim
+        alice.total_stake = threshold - 1;
+        assert!(state.release_ownership(&tile, &alice).unwrap());
+        assert!(state.try_claim_ownership(&tile, &bob).unwrap());
+        assert_eq!(state.owner, bob.owner);
+        assert_eq!(state.owner_stake, threshold);
+    }
+
+    #[test]
+    fn test_owner_keeps_property_above_threshold() {
+        let tile = CLASSIC_TILES[1];
+        let threshold = PropertyState::ownership_threshold(&tile).unwrap();
+        let mut state = property_state(0);
+        let mut alice = staker(Pubkey::new_unique(), 3 * threshold);
+        let bob = staker(Pubkey::new_unique(), 2 * threshold);
+
+        state.try_claim_ownership(&tile, &alice).unwrap();
+
+        alice.total_stake = threshold;
+        assert!(!state.release_ownership(&tile, &alice).unwrap());
+        assert_eq!(state.owner_stake, threshold);
+
+        // Not the owner anymore, bob's larger total takes over
+        assert!(state.try_claim_ownership(&tile, &bob).unwrap());
+        assert_eq!(state.owner, bob.owner);
+    }
+
+    #[test]
+    fn test_distribute_rent_without_stakers() {
+        let mut state = property_state(0);
+
+        assert!(!state.distribute_rent(1_000).unwrap());
+        assert_eq!(state.rent_per_share, 0);
+        assert_eq!(state.total_rent, 0);
+    }
+
+    #[test]
+    fn test_rent_is_split_pro_rata() {
+        let mut state = property_state(4_000);
+        let mut small = property_stake(1_000);
+        let mut large = property_stake(3_000);
+        small.checkpoint_rent(&state).unwrap();
+        large.checkpoint_rent(&state).unwrap();
+
+        assert!(state.distribute_rent(1_000).unwrap());
+        assert_eq!(state.total_rent, 1_000);
+        assert_eq!(small.pending_rent(&state).unwrap(), 250);
+        assert_eq!(large.pending_rent(&state).unwrap(), 750);
+
+        // Claiming checkpoints the stake, only later rent is pending
+        small.checkpoint_rent(&state).unwrap();
+        assert_eq!(small.pending_rent(&state).unwrap(), 0);
+
+        state.distribute_rent(400).unwrap();
+        assert_eq!(small.pending_rent(&state).unwrap(), 100);
+        assert_eq!(large.pending_rent(&state).unwrap(), 1_050);
+    }
+
+    #[test]
+    fn test_new_stakes_only_earn_later_rent() {
+        let mut state = property_state(1_000);
+        state.distribute_rent(1_000).unwrap();
+
+        let mut stake = property_stake(1_000);
+        state.total_investment += stake.stake_amount;
+        stake.checkpoint_rent(&state).unwrap();
+        assert_eq!(stake.pending_rent(&state).unwrap(), 0);
+
+        state.distribute_rent(1_000).unwrap();
+        assert_eq!(stake.pending_rent(&state).unwrap(), 500);
+    }
+
+    #[test]
+    fn test_pending_rent_rounds_down() {
+        let mut state = property_state(3);
+        let stake = property_stake(1);
+
+        state.distribute_rent(1).unwrap();
+        assert_eq!(stake.pending_rent(&state).unwrap(), 0);
+
+        state.distribute_rent(2).unwrap();
+        assert_eq!(stake.pending_rent(&state).unwrap(), 0);
+
+        state.distribute_rent(1).unwrap();
+        assert_eq!(stake.pending_rent(&state).unwrap(), 1);
+    }
+}
@@ -1,7 +1,8 @@
 /// Import necessary modules from crate
 use crate::{
-    consts::DECIMALS_S,
-    err::AmmError,
+    consts::{DECIMALS_S, MEME_TOKEN_DECIMALS},
+    err::{self, AmmError},
+    libraries::MulDiv,
     math::utils::{multiply_divide, CheckedMath, CheckedMath256},
 };
 
@@ -18,7 +19,37 @@ use spl_math::uint::U256;
 use std::{cmp::min, mem};
 
 /// Import related models
-use super::{fees::Fees, Reserve, SwapAmount};
+use super::{
+    fees::{Fees, FEE_PRECISION, SAME_SLOT_SELL_FEE},
+    Reserve, SwapAmount,
+};
+
+/// Precision of [`BoundPool::spot_price`]
+pub const PRICE_PRECISION: u128 = 1_000_000_000_000;
+
+/// Basis points in a whole
+pub const BPS_DENOM: u64 = 10_000;
+
+/// Steps of the search for the largest partial fill within a [`PriceLimit`],
+/// the fill is exact to `coin_in_amount / 2^PARTIAL_FILL_SEARCH_STEPS`
+pub const PARTIAL_FILL_SEARCH_STEPS: u32 = 24;
+
+/// Price bounds of a swap, prices are in quote tokens per whole meme token
+/// scaled by [`PRICE_PRECISION`], like [`BoundPool::spot_price`]
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub struct PriceLimit {
+    /// Highest average price paid on a buy, lowest average price received on
+    /// a sell, fees included
+    pub avg_price: Option<u128>,
+    /// Highest move of the spot price caused by the swap, in basis points
+    pub max_price_impact_bps: Option<u64>,
+}
+
+impl PriceLimit {
+    pub fn is_none(&self) -> bool {
+        self.avg_price.is_none() && self.max_price_impact_bps.is_none()
+    }
+}
 
 /// Account struct representing a bonding curve pool
 #[account]
@@ -46,6 +77,18 @@ pub struct BoundPool {
     pub locked: bool,
     /// Vesting period duration
     pub vesting_period: i64,
+    /// Flag indicating the meme mint authority was revoked, i.e. the supply
+    /// can never grow past what `new_pool` minted
+    pub supply_fixed: bool,
+    /// Slot of the pool's last buy, sells in the same slot pay
+    /// `SAME_SLOT_SELL_FEE` on top of the quote fee
+    pub last_buy_slot: u64,
+    /// Flag indicating swaps pay out and take meme tokens from token accounts
+    /// instead of meme tickets
+    pub liquid: bool,
+    /// Meme tokens bought into meme tickets and property stakes, which stay in
+    /// the meme vault until they're sold back or withdrawn
+    pub ticket_tokens: u64,
 }
 
 impl BoundPool {
@@ -79,6 +122,14 @@ impl BoundPool {
         let locked = 1;
         /// Size of airdropped tokens counter
         let airdropped_tokens = 8;
+        /// Size of supply fixed flag
+        let supply_fixed = 1;
+        /// Size of last buy slot
+        let last_buy_slot = 8;
+        /// Size of liquid flag
+        let liquid = 1;
+        /// Size of ticket tokens counter
+        let ticket_tokens = 8;
         /// Extra padding space
         let padding = 128;
 
@@ -94,6 +145,10 @@ impl BoundPool {
             + config
             + locked
             + airdropped_tokens
+            + supply_fixed
+            + last_buy_slot
+            + liquid
+            + ticket_tokens
             + padding
     }
 }
@@ -136,27 +191,259 @@ impl BoundPool {
         coin_in_amount: u64,
         coin_out_min_value: u64,
         buy_meme: bool,
-    ) -> SwapAmount {
+    ) -> Result<SwapAmount> {
+        self.swap_amounts_with_fees(coin_in_amount, coin_out_min_value, buy_meme, &self.fees)
+    }
+
+    /// Same as [`BoundPool::swap_amounts`], but charges the given fees instead
+    /// of the pool's own, e.g. after a points-burn discount was applied.
+    pub fn swap_amounts_with_fees(
+        &self,
+        coin_in_amount: u64,
+        coin_out_min_value: u64,
+        buy_meme: bool,
+        fees: &Fees,
+    ) -> Result<SwapAmount> {
+        if buy_meme {
+            self.buy_meme_swap_amounts(coin_in_amount, coin_out_min_value, fees)
+        } else {
+            self.sell_meme_swap_amounts(coin_in_amount, coin_out_min_value, fees)
+        }
+    }
+
+    /// Same as [`BoundPool::swap_amounts_with_fees`], but fills only the
+    /// largest part of `coin_in_amount` that stays within `limit` instead of
+    /// failing. `coin_out_min_value` applies to the filled part.
+    ///
+    /// Fails with `PriceLimitExceeded` if no part of the swap is within
+    /// `limit`.
+    pub fn limited_swap_amounts(
+        &self,
+        coin_in_amount: u64,
+        coin_out_min_value: u64,
+        buy_meme: bool,
+        fees: &Fees,
+        limit: &PriceLimit,
+    ) -> Result<SwapAmount> {
+        if limit.is_none() {
+            return self.swap_amounts_with_fees(coin_in_amount, coin_out_min_value, buy_meme, fees);
+        }
+
+        let mut swap_amount = self.swap_amounts_with_fees(coin_in_amount, 0, buy_meme, fees)?;
+
+        if !self.within_price_limit(&swap_amount, buy_meme, limit)? {
+            // Both the average price and the price impact only get worse with
+            // the size of the swap, `lo` is always within the limit, `hi` never
+            let (mut lo, mut hi) = (0, coin_in_amount);
+            let mut filled = None;
+
+            for _ in 0..PARTIAL_FILL_SEARCH_STEPS {
+                if hi - lo <= 1 {
+                    break;
+                }
+
+                let mid = lo + (hi - lo) / 2;
+                let candidate = self.swap_amounts_with_fees(mid, 0, buy_meme, fees)?;
+
+                if self.within_price_limit(&candidate, buy_meme, limit)? {
+                    lo = mid;
+                    filled = Some(candidate);
+                } else {
+                    hi = mid;
+                }
+            }
+
+            swap_amount = filled.ok_or(AmmError::PriceLimitExceeded)?;
+        }
+
+        if swap_amount.amount_out < coin_out_min_value {
+            return Err(error!(AmmError::SlippageExceeded));
+        }
+
+        Ok(swap_amount)
+    }
+
+    /// Runs a buy of meme tokens against the curve and applies it to the
+    /// pool's reserves and fee balances, locking the pool once its meme
+    /// reserve is depleted.
+    ///
+    /// This is the single buy path shared by every instruction that buys
+    /// from the curve, callers only move the tokens. The buy is partially
+    /// filled within `limit`, see [`BoundPool::limited_swap_amounts`], and
+    /// recorded at `slot`, see [`BoundPool::sell_fees`].
+    pub fn execute_buy(
+        &mut self,
+        coin_in_amount: u64,
+        coin_x_min_value: u64,
+        fees: &Fees,
+        limit: &PriceLimit,
+        slot: u64,
+    ) -> Result<SwapAmount> {
+        if coin_in_amount == 0 {
+            return Err(error!(AmmError::NoZeroTokens));
+        }
+
+        if self.locked {
+            return Err(error!(AmmError::PoolIsLocked));
+        }
+
+        let swap_amount =
+            self.limited_swap_amounts(coin_in_amount, coin_x_min_value, true, fees, limit)?;
+
+        self.apply_swap(&swap_amount, true)?;
+        self.last_buy_slot = slot;
+
+        if self.meme_reserve.tokens == 0 {
+            self.locked = true;
+        }
+
+        Ok(swap_amount)
+    }
+
+    /// Fees charged for a sell at `slot`.
+    ///
+    /// Sell outputs are already rounded in favor of the pool, so a buy and a
+    /// sell can never return more than was paid. A sell in the same slot as
+    /// the last buy additionally pays `SAME_SLOT_SELL_FEE`, which makes
+    /// sandwiching a buy cost more than the price move it captures.
+    pub fn sell_fees(&self, fees: &Fees, slot: u64) -> Result<Fees> {
+        if slot != self.last_buy_slot {
+            return Ok(*fees);
+        }
+
+        Ok(Fees {
+            fee_meme_percent: fees.fee_meme_percent,
+            fee_quote_percent: min(
+                fees.fee_quote_percent
+                    .checked_add(SAME_SLOT_SELL_FEE)
+                    .ok_or(AmmError::MathOverflow)?,
+                FEE_PRECISION,
+            ),
+        })
+    }
+
+    /// Applies a swap computed by [`BoundPool::swap_amounts`] to the pool's
+    /// reserves and fee balances.
+    ///
+    /// Every update is checked, swap amounts that don't fit the pool fail with
+    /// `InvariantViolation` instead of wrapping its balances.
+    pub fn apply_swap(&mut self, swap_amount: &SwapAmount, buy_meme: bool) -> Result<()> {
         if buy_meme {
-            self.buy_meme_swap_amounts(coin_in_amount, coin_out_min_value)
-                .unwrap()
+            self.admin_fees_quote = credit(self.admin_fees_quote, swap_amount.admin_fee_in)?;
+            self.admin_fees_meme = credit(self.admin_fees_meme, swap_amount.admin_fee_out)?;
+
+            self.quote_reserve.tokens = credit(self.quote_reserve.tokens, swap_amount.amount_in)?;
+            self.meme_reserve.tokens = debit(
+                self.meme_reserve.tokens,
+                credit(swap_amount.amount_out, swap_amount.admin_fee_out)?,
+            )?;
         } else {
-            self.sell_meme_swap_amounts(coin_in_amount, coin_out_min_value)
-                .unwrap()
+            self.admin_fees_meme = credit(self.admin_fees_meme, swap_amount.admin_fee_in)?;
+            self.admin_fees_quote = credit(self.admin_fees_quote, swap_amount.admin_fee_out)?;
+
+            self.meme_reserve.tokens = credit(self.meme_reserve.tokens, swap_amount.amount_in)?;
+            self.quote_reserve.tokens = debit(
+                self.quote_reserve.tokens,
+                credit(swap_amount.amount_out, swap_amount.admin_fee_out)?,
+            )?;
         }
+
+        Ok(())
+    }
+
+    /// Checks that the quote vault holds at least the quote reserve plus the
+    /// quote admin fees, i.e. that the pool never accounts for more than it
+    /// was paid. Call after the swap's transfers, with the reloaded balance.
+    pub fn check_vault_invariant(&self, quote_vault_balance: u64) -> Result<()> {
+        self.quote_vault_surplus(quote_vault_balance)?;
+
+        Ok(())
+    }
+
+    /// Records meme tokens bought into a meme ticket or property stake, which
+    /// stay in the meme vault.
+    pub fn hold_ticket_tokens(&mut self, amount: u64) -> Result<()> {
+        self.ticket_tokens = credit(self.ticket_tokens, amount)?;
+
+        Ok(())
     }
 
-    fn buy_meme_swap_amounts(&self, delta_s: u64, min_delta_m: u64) -> Result<SwapAmount> {
+    /// Releases meme tokens of a ticket or property stake sold back into the
+    /// curve. Pools created before tickets were counted undercount them, so
+    /// this saturates instead of failing.
+    pub fn release_ticket_tokens(&mut self, amount: u64) {
+        self.ticket_tokens = self.ticket_tokens.saturating_sub(amount);
+    }
+
+    /// Quote tokens in the vault the pool doesn't account for, e.g. tokens
+    /// sent straight to the vault. Fails on a deficit.
+    pub fn quote_vault_surplus(&self, quote_vault_balance: u64) -> Result<u64> {
+        let accounted = credit(self.quote_reserve.tokens, self.admin_fees_quote)?;
+
+        if quote_vault_balance < accounted {
+            msg!(
+                "[InvariantViolation] quote vault: {}, reserve and fees: {}",
+                quote_vault_balance,
+                accounted
+            );
+
+            return Err(error!(AmmError::InvariantViolation));
+        }
+
+        Ok(quote_vault_balance - accounted)
+    }
+
+    /// Meme tokens in the vault the pool doesn't account for. The vault backs
+    /// the curve reserve, the meme admin fees, the tokens of tickets and
+    /// property stakes, and the LP tokens, which include the airdrop. Fails on
+    /// a deficit.
+    pub fn meme_vault_surplus(&self, meme_vault_balance: u64) -> Result<u64> {
+        let accounted = credit(
+            credit(self.meme_reserve.tokens, self.admin_fees_meme)?,
+            credit(self.ticket_tokens, self.config.omega_m)?,
+        )?;
+
+        if meme_vault_balance < accounted {
+            msg!(
+                "[InvariantViolation] meme vault: {}, reserve, fees, tickets and LP: {}",
+                meme_vault_balance,
+                accounted
+            );
+
+            return Err(error!(AmmError::InvariantViolation));
+        }
+
+        Ok(meme_vault_balance - accounted)
+    }
+
+    fn buy_meme_swap_amounts(
+        &self,
+        delta_s: u64,
+        min_delta_m: u64,
+        fees: &Fees,
+    ) -> Result<SwapAmount> {
         let (m_t0, s_t0) = self.balances();
 
         let p = &self.config;
 
-        let max_delta_s = p.gamma_s - s_t0;
+        let max_delta_s = p.gamma_s.checked_sub(s_t0).ok_or(AmmError::MathOverflow)?;
+
+        let admin_fee_in = fees.get_fee_quote_amount(delta_s)?;
+        let delta_s_after_fee = delta_s
+            .checked_sub(admin_fee_in)
+            .ok_or(AmmError::MathOverflow)?;
+        let is_max = delta_s_after_fee >= max_delta_s;
 
-        let admin_fee_in = self.fees.get_fee_quote_amount(delta_s).unwrap();
-        let is_max = delta_s - admin_fee_in >= max_delta_s;
+        // A buy crossing `gamma_s` only uses the quote needed to complete the
+        // curve, the fee is charged on that part alone and the rest is never
+        // taken from the buyer
+        let (net_delta_s, admin_fee_in) = if is_max {
+            let used_delta_s = fees.get_gross_quote_amount(max_delta_s)?;
 
-        let net_delta_s = min(delta_s - admin_fee_in, max_delta_s);
+            (max_delta_s, used_delta_s - max_delta_s)
+        } else {
+            (delta_s_after_fee, admin_fee_in)
+        };
 
         let delta_m = if is_max {
             m_t0
@@ -164,8 +451,10 @@ impl BoundPool {
             self.compute_delta_m(s_t0, s_t0 + net_delta_s)?
         };
 
-        let admin_fee_out = self.fees.get_fee_meme_amount(delta_m).unwrap();
-        let net_delta_m = delta_m - admin_fee_out;
+        let admin_fee_out = fees.get_fee_meme_amount(delta_m)?;
+        let net_delta_m = delta_m
+            .checked_sub(admin_fee_out)
+            .ok_or(AmmError::MathOverflow)?;
 
         if net_delta_m < min_delta_m {
             return Err(error!(AmmError::SlippageExceeded));
@@ -179,17 +468,26 @@ impl BoundPool {
         })
     }
 
-    fn sell_meme_swap_amounts(&self, delta_m: u64, min_delta_s: u64) -> Result<SwapAmount> {
+    fn sell_meme_swap_amounts(
+        &self,
+        delta_m: u64,
+        min_delta_s: u64,
+        fees: &Fees,
+    ) -> Result<SwapAmount> {
         let (m_b, s_b) = self.balances();
 
         let p = &self.config;
 
-        let max_delta_m = p.gamma_m - m_b; // TODO: confirm
+        // Only the tokens bought from the curve can be sold back into it
+        let max_delta_m = p.gamma_m.checked_sub(m_b).ok_or(AmmError::MathOverflow)?;
 
-        let admin_fee_in = self.fees.get_fee_meme_amount(delta_m).unwrap() * 2;
-        let is_max = delta_m - admin_fee_in >= max_delta_m;
+        let admin_fee_in = fees.get_fee_meme_amount(delta_m)?;
+        let delta_m_after_fee = delta_m
+            .checked_sub(admin_fee_in)
+            .ok_or(AmmError::MathOverflow)?;
+        let is_max = delta_m_after_fee >= max_delta_m;
 
-        let net_delta_m = min(delta_m - admin_fee_in, max_delta_m);
+        let net_delta_m = min(delta_m_after_fee, max_delta_m);
 
         let delta_s = if is_max {
             s_b
@@ -197,10 +495,11 @@ impl BoundPool {
             self.compute_delta_s(s_b, net_delta_m)?
         };
 
-        let admin_fee_out = self.fees.get_fee_quote_amount(delta_s).unwrap() * 2;
-        let net_delta_s = delta_s - admin_fee_out;
+        let admin_fee_out = fees.get_fee_quote_amount(delta_s)?;
+        let net_delta_s = delta_s
+            .checked_sub(admin_fee_out)
+            .ok_or(AmmError::MathOverflow)?;
 
-        //assert!(net_delta_s >= min_delta_s, errors::slippage());
         if net_delta_s < min_delta_s {
             return Err(error!(AmmError::SlippageExceeded));
         }
@@ -248,11 +547,188 @@ impl BoundPool {
         }
     }
 
+    /// Marginal price of one whole meme token in quote tokens at the current
+    /// quote reserve, scaled by [`PRICE_PRECISION`]
+    pub fn spot_price(&self) -> Result<u128> {
+        self.spot_price_at(self.quote_reserve.tokens)
+    }
+
+    /// Same as [`BoundPool::spot_price`], at a quote reserve of `s`
+    pub fn spot_price_at(&self, s: u64) -> Result<u128> {
+        let (num, denom) = self.marginal_price(s)?;
+
+        let price = num
+            .checked_mul(U256::from(MEME_TOKEN_DECIMALS))
+            .checked_mul(U256::from(PRICE_PRECISION))
+            .checked_div(denom)
+            .ok_or(AmmError::MathOverflow)?;
+
+        if price > U256::from(u128::MAX) {
+            return Err(error!(AmmError::MathOverflow));
+        }
+
+        Ok(price.as_u128())
+    }
+
+    /// Value of the whole meme supply at the spot price, in quote tokens
+    pub fn market_cap(&self) -> Result<u64> {
+        let (num, denom) = self.marginal_price(self.quote_reserve.tokens)?;
+
+        let total_supply = self
+            .config
+            .gamma_m
+            .checked_add(self.config.omega_m)
+            .ok_or(AmmError::MathOverflow)?;
+
+        let market_cap = num
+            .checked_mul(U256::from(total_supply))
+            .checked_div(denom)
+            .ok_or(AmmError::MathOverflow)?;
+
+        if market_cap > U256::from(u64::MAX) {
+            return Err(error!(AmmError::MathOverflow));
+        }
+
+        Ok(market_cap.as_u64())
+    }
+
+    /// How far the quote reserve moved toward `gamma_s`, in basis points
+    pub fn progress_bps(&self) -> Result<u64> {
+        let progress = self
+            .quote_reserve
+            .tokens
+            .mul_div_floor(BPS_DENOM, self.config.gamma_s)
+            .ok_or(AmmError::MulDivOverflow)?;
+
+        Ok(min(progress, BPS_DENOM))
+    }
+
+    /// Quote tokens still to be raised before the curve is complete
+    pub fn remaining_to_graduation(&self) -> u64 {
+        self.config
+            .gamma_s
+            .saturating_sub(self.quote_reserve.tokens)
+    }
+
+    /// Average price of a swap of `quote` for `meme` tokens, in the unit of
+    /// [`BoundPool::spot_price`]
+    pub fn avg_price(quote: u64, meme: u64) -> Result<u128> {
+        if meme == 0 {
+            return Err(error!(AmmError::DivideByZero));
+        }
+
+        Ok((quote as u128)
+            .checked_mul(MEME_TOKEN_DECIMALS as u128)
+            .checked_mul(PRICE_PRECISION)
+            .checked_div(meme as u128)
+            .ok_or(AmmError::MathOverflow)?)
+    }
+
+    /// Whether the average price and the price impact of a swap computed by
+    /// [`BoundPool::swap_amounts`] are within `limit`
+    fn within_price_limit(
+        &self,
+        swap_amount: &SwapAmount,
+        buy_meme: bool,
+        limit: &PriceLimit,
+    ) -> Result<bool> {
+        let s = self.quote_reserve.tokens;
+
+        // Quote and meme tokens the user pays or receives, and the quote
+        // reserve after the swap
+        let (quote, meme, s_after) = if buy_meme {
+            (
+                credit(swap_amount.amount_in, swap_amount.admin_fee_in)?,
+                swap_amount.amount_out,
+                credit(s, swap_amount.amount_in)?,
+            )
+        } else {
+            (
+                swap_amount.amount_out,
+                credit(swap_amount.amount_in, swap_amount.admin_fee_in)?,
+                debit(s, credit(swap_amount.amount_out, swap_amount.admin_fee_out)?)?,
+            )
+        };
+
+        if let Some(limit_price) = limit.avg_price {
+            if meme == 0 {
+                return Ok(false);
+            }
+
+            let avg_price = Self::avg_price(quote, meme)?;
+
+            if (buy_meme && avg_price > limit_price) || (!buy_meme && avg_price < limit_price) {
+                return Ok(false);
+            }
+        }
+
+        if let Some(max_price_impact_bps) = limit.max_price_impact_bps {
+            let price_before = self.spot_price_at(s)?;
+            let price_after = self.spot_price_at(s_after)?;
+
+            let price_impact_bps = price_before
+                .abs_diff(price_after)
+                .checked_mul(BPS_DENOM as u128)
+                .checked_div(price_before)
+                .ok_or(AmmError::MathOverflow)?;
+
+            if price_impact_bps > max_price_impact_bps as u128 {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Price of one meme token unit in quote token units at a quote reserve
+    /// of `s`, as a fraction. The curve gives the meme tokens released per
+    /// quote token, `beta / D - alpha * s / D^2`, the price is its inverse.
+    fn marginal_price(&self, s: u64) -> Result<(U256, U256)> {
+        let p = &self.config;
+        let decimals_s = U256::from(DECIMALS_S);
+        let alpha_decimals = U256::from(p.decimals.alpha);
+        let beta_decimals = U256::from(p.decimals.beta);
+
+        let num = alpha_decimals
+            .checked_mul(beta_decimals)
+            .checked_mul(decimals_s)
+            .checked_mul(decimals_s)
+            .ok_or(AmmError::MathOverflow)?;
+
+        let denom = U256::from(p.beta)
+            .checked_mul(alpha_decimals)
+            .checked_mul(decimals_s)
+            .checked_sub_(
+                U256::from(p.alpha_abs)
+                    .checked_mul(beta_decimals)
+                    .checked_mul(U256::from(s)),
+            )
+            .ok_or(AmmError::MathOverflow)?;
+
+        if denom.is_zero() {
+            return Err(error!(AmmError::DivideByZero));
+        }
+
+        Ok((num, denom))
+    }
+
     fn balances(&self) -> (u64, u64) {
         (self.meme_reserve.tokens, self.quote_reserve.tokens)
     }
 }
 
+/// Fails with `Expired` once `now` is past the swap's deadline, if it has one
+pub fn check_deadline(deadline_ts: Option<i64>, now: i64) -> Result<()> {
+    match deadline_ts {
+        Some(deadline_ts) if now > deadline_ts => {
+            msg!("[Expired] deadline: {}, now: {}", deadline_ts, now);
+
+            Err(error!(AmmError::Expired))
+        }
+        _ => Ok(()),
+    }
+}
+
 pub fn compute_alpha_abs(
     gamma_s: u128,
     gamma_s_denom: u128,
@@ -261,19 +737,24 @@ pub fn compute_alpha_abs(
     price_factor_num: u64,
     price_factor_denom: u64,
 ) -> Result<(u128, u128)> {
+    check_supply_split(gamma_m, omega_m, price_factor_denom)?;
     check_slope(gamma_m, omega_m, price_factor_num, price_factor_denom)?;
 
-    let left = omega_m
-        .checked_mul(price_factor_num as u128)
-        .checked_div(price_factor_denom as u128)
-        .unwrap();
-    let num = U256::from(2 * (gamma_m - left)) * U256::from(gamma_s_denom * gamma_s_denom);
-    let denom = U256::from(gamma_s * gamma_s);
+    let left = price_factored_omega_m(omega_m, price_factor_num, price_factor_denom)?;
+    // Squares of u128 values always fit into U256
+    let num = (U256::from(gamma_s_denom) * U256::from(gamma_s_denom))
+        .checked_mul(U256::from(2 * (gamma_m - left)))
+        .ok_or(AmmError::MathOverflow)?;
+    let denom = U256::from(gamma_s) * U256::from(gamma_s);
 
     if num <= denom {
         return Err(error!(AmmError::EGammaSAboveRelativeLimit));
     }
 
+    if num > U256::from(u128::MAX) {
+        return Err(error!(AmmError::MathOverflow));
+    }
+
     // Calculate the scale (order of magnitude) of numerator and denominator
     // This helps prevent overflow when computing alpha
     let num_scale = compute_scale(num.as_u128());
@@ -287,10 +768,16 @@ pub fn compute_alpha_abs(
     let alpha_decimals = U256::from(compute_decimals(net_scale)?);
 
     // We compute |alpha|, hence the subtraction is switched
-    Ok((
-        ((num * alpha_decimals) / denom).as_u128(),
-        alpha_decimals.as_u128(),
-    ))
+    let alpha_abs = num
+        .checked_mul(alpha_decimals)
+        .checked_div(denom)
+        .ok_or(AmmError::MathOverflow)?;
+
+    if alpha_abs > U256::from(u128::MAX) {
+        return Err(error!(AmmError::MathOverflow));
+    }
+
+    Ok((alpha_abs.as_u128(), alpha_decimals.as_u128()))
 }
 
 pub fn compute_decimals(scale: u64) -> Result<u128> {
@@ -317,18 +804,37 @@ pub fn compute_beta(
     price_factor_denom: u64,
     beta_decimals: u128,
 ) -> Result<u128> {
+    check_supply_split(gamma_m, omega_m, price_factor_denom)?;
     check_intercept(gamma_m, omega_m, price_factor_num, price_factor_denom)?;
 
-    let left = 2 * gamma_m;
-    let right = omega_m
-        .checked_mul(price_factor_num as u128)
-        .checked_div(price_factor_denom as u128)
-        .unwrap();
+    let left = gamma_m.checked_mul(2).ok_or(AmmError::MathOverflow)?;
+    let right = price_factored_omega_m(omega_m, price_factor_num, price_factor_denom)?;
 
-    let num = (left - right) * gamma_s_denom;
+    let num = (left - right)
+        .checked_mul(gamma_s_denom)
+        .checked_mul(beta_decimals)
+        .ok_or(AmmError::MathOverflow)?;
     let denom = gamma_s;
 
-    Ok((num * beta_decimals) / denom)
+    if denom == 0 {
+        return Err(error!(AmmError::DivideByZero));
+    }
+
+    Ok(num / denom)
+}
+
+/// Sam's supply split has to leave tokens on both sides of the curve and the
+/// price factor has to be a proper fraction.
+pub fn check_supply_split(gamma_m: u128, omega_m: u128, price_factor_denom: u64) -> Result<()> {
+    if gamma_m == 0 || omega_m == 0 {
+        return Err(error!(err::arg("Trading and LP supplies must be non-zero")));
+    }
+
+    if price_factor_denom == 0 {
+        return Err(error!(err::arg("Price factor denominator must be non-zero")));
+    }
+
+    Ok(())
 }
 
 pub fn check_slope(
@@ -337,10 +843,7 @@ pub fn check_slope(
     price_factor_num: u64,
     price_factor_denom: u64,
 ) -> Result<()> {
-    let pfo = omega_m
-        .checked_mul(price_factor_num as u128)
-        .checked_div(price_factor_denom as u128)
-        .unwrap();
+    let pfo = price_factored_omega_m(omega_m, price_factor_num, price_factor_denom)?;
     // TODO: For positive slope bonding curve in future:
     // - Change this check to `if pfo <= gamma_m` since positive slope means
     //   price increases as supply increases (opposite of current negative slope)
@@ -360,22 +863,48 @@ pub fn check_intercept(
     price_factor_num: u64,
     price_factor_denom: u64,
 ) -> Result<()> {
-    let omp = omega_m
-        .checked_mul(price_factor_num as u128)
-        .checked_div(price_factor_denom as u128)
-        .unwrap();
+    let omp = price_factored_omega_m(omega_m, price_factor_num, price_factor_denom)?;
+    let double_gamma_m = gamma_m.checked_mul(2).ok_or(AmmError::MathOverflow)?;
     // TODO: For positive slope bonding curve in future:
     // - Change this check to `if 2 * gamma_m >= omp` since positive slope means
     //   intercept must be negative (opposite of current negative slope case)
     // - Update error message to BondingCurveInterceptMustBeNegative
     // - Update related slope checks in check_slope()
-    if 2 * gamma_m <= omp {
+    if double_gamma_m <= omp {
         return Err(error!(AmmError::BondingCurveInterceptMustBePositive)); 
     }
 
     Ok(())
 }
 
+/// LP tokens scaled by the price factor, i.e. `omega_m * num / denom`
+fn price_factored_omega_m(
+    omega_m: u128,
+    price_factor_num: u64,
+    price_factor_denom: u64,
+) -> Result<u128> {
+    if price_factor_denom == 0 {
+        return Err(error!(AmmError::DivideByZero));
+    }
+
+    Ok(omega_m
+        .checked_mul(price_factor_num as u128)
+        .checked_div(price_factor_denom as u128)
+        .ok_or(AmmError::MathOverflow)?)
+}
+
+fn credit(balance: u64, amount: u64) -> Result<u64> {
+    Ok(balance
+        .checked_add(amount)
+        .ok_or(AmmError::InvariantViolation)?)
+}
+
+fn debit(balance: u64, amount: u64) -> Result<u64> {
+    Ok(balance
+        .checked_sub(amount)
+        .ok_or(AmmError::InvariantViolation)?)
+}
+
 fn compute_scale(num_: u128) -> u64 {
     let mut num = num_;
 
@@ -447,24 +976,38 @@ fn delta_s_strategy(
     }
     let a = a.unwrap();
 
-    let b = v
-        .checked_pow(U256::from(2))
-        .checked_mul(alpha_decimals)
-        .sqrt();
+    let b_squared = v.checked_pow(U256::from(2)).checked_mul(alpha_decimals);
 
-    if let None = b {
+    if let None = b_squared {
         return None;
     }
-    let b = b.unwrap();
+    let b_squared = b_squared.unwrap();
 
-    let num_1 = vec![decimals_s, alpha_decimals, a, v];
-    let num_2 = vec![decimals_s, alpha_decimals, u, b];
-    let denom_ = vec![U256::from(2), alpha_abs, b, v];
+    // `a` is rounded down, `b` and the right term are rounded up, so that the
+    // quote tokens released never exceed the exact integral of the curve
+    let b = b_squared.integer_sqrt();
+    let b = if b * b < b_squared { b + 1 } else { b };
 
-    let left = multiply_divide(num_1, denom_.clone());
-    let right = multiply_divide(num_2, denom_);
+    let left = multiply_divide(
+        vec![decimals_s, alpha_decimals, a],
+        vec![U256::from(2), alpha_abs, b],
+    );
 
-    left.checked_sub_(right).map(|value| value.as_u128())
+    let right_num = decimals_s.checked_mul(alpha_decimals).checked_mul(u);
+    let right_denom = U256::from(2).checked_mul(alpha_abs).checked_mul(v);
+
+    if let None = right_denom {
+        return None;
+    }
+    let right_denom = right_denom.unwrap();
+
+    let right = right_num
+        .checked_add_(right_denom.checked_sub(U256::from(1)))
+        .checked_div(right_denom);
+
+    left.checked_sub_(right)
+        .filter(|value| *value <= U256::from(u128::MAX))
+        .map(|value| value.as_u128())
 }
 
 fn compute_a(u: U256, alpha_decimals: U256, w: U256, v: U256, scale: U256) -> Option<U256> {
@@ -487,7 +1030,7 @@ fn compute_a(u: U256, alpha_decimals: U256, w: U256, v: U256, scale: U256) -> Op
             alpha_decimals,
             w,
             v,
-            scale.checked_mul(U256::from(100)).unwrap(),
+            scale.checked_mul(U256::from(100))?,
         ),
     }
 }
@@ -553,12 +1096,14 @@ fn delta_m1_strategy(
     if let None = left {
         return None;
     }
+    // Rounded up, so that the difference is rounded down in favor of the pool
+    let right_denom = DECIMALS_S.checked_pow(2).checked_mul(2 * alpha_decimals);
     let right = s_b
         .checked_pow(2)
         .checked_sub_(s_a.checked_pow(2))
         .checked_mul(alpha_abs)
-        .checked_div_(DECIMALS_S.checked_pow(2))
-        .checked_div(2 * alpha_decimals);
+        .checked_add_(right_denom.checked_sub(1))
+        .checked_div_(right_denom);
 
     if let None = right {
         return None;
@@ -566,3 +1111,607 @@ fn delta_m1_strategy(
 
     left.checked_sub_(right)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::consts::{
+        DEFAULT_MAX_M, DEFAULT_MAX_M_LP, DEFAULT_PRICE_FACTOR_DENOMINATOR,
+        DEFAULT_PRICE_FACTOR_NUMERATOR,
+    };
+    use crate::math::{TryAdd, TryDiv, TryMul, TrySqrt, TrySub};
+    use crate::models::fees::{FEE, MEME_FEE};
+    use proptest::prelude::*;
+
+    const SOL: u64 = 1_000_000_000;
+
+    /// Quote units `compute_delta_s` may fall short of the exact inverse
+    const DELTA_S_ROUNDING: u64 = 2;
+
+    /// Fresh pool with the default tokenomics and a quote mint of 9 decimals
+    fn new_pool(gamma_s: u64) -> BoundPool {
+        let gamma_s_denom = DECIMALS_S;
+
+        let (alpha_abs, decimals) = compute_alpha_abs(
+            gamma_s as u128,
+            gamma_s_denom,
+            DEFAULT_MAX_M,
+            DEFAULT_MAX_M_LP,
+            DEFAULT_PRICE_FACTOR_NUMERATOR,
+            DEFAULT_PRICE_FACTOR_DENOMINATOR,
+        )
+        .unwrap();
+
+        let beta = compute_beta(
+            gamma_s as u128,
+            gamma_s_denom,
+            DEFAULT_MAX_M,
+            DEFAULT_MAX_M_LP,
+            DEFAULT_PRICE_FACTOR_NUMERATOR,
+            DEFAULT_PRICE_FACTOR_DENOMINATOR,
+            decimals,
+        )
+        .unwrap();
+
+        BoundPool {
+            meme_reserve: Reserve {
+                tokens: DEFAULT_MAX_M as u64,
+                ..Default::default()
+            },
+            fees: Fees {
+                fee_meme_percent: MEME_FEE,
+                fee_quote_percent: FEE,
+            },
+            config: Config {
+                alpha_abs,
+                beta,
+                price_factor_num: DEFAULT_PRICE_FACTOR_NUMERATOR,
+                price_factor_denom: DEFAULT_PRICE_FACTOR_DENOMINATOR,
+                gamma_s,
+                gamma_m: DEFAULT_MAX_M as u64,
+                omega_m: DEFAULT_MAX_M_LP as u64,
+                decimals: Decimals {
+                    alpha: decimals,
+                    beta: decimals,
+                    quote: gamma_s_denom as u64,
+                },
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Pool whose curve already moved to a quote reserve of `s_a`
+    fn pool_at(gamma_s: u64, s_a: u64) -> BoundPool {
+        let mut pool = new_pool(gamma_s);
+
+        let sold = pool.compute_delta_m(0, s_a).unwrap();
+        pool.quote_reserve.tokens = s_a;
+        pool.meme_reserve.tokens -= sold;
+
+        pool
+    }
+
+    /// Exact `floor(delta_m)` of the pool's integer curve parameters
+    fn exact_delta_m(pool: &BoundPool, s_a: u64, s_b: u64) -> u128 {
+        let p = &pool.config;
+        let d = U256::from(DECIMALS_S);
+        let (s_a, s_b) = (U256::from(s_a), U256::from(s_b));
+
+        let linear = U256::from(2)
+            * U256::from(p.beta)
+            * d
+            * U256::from(p.decimals.alpha)
+            * (s_b - s_a);
+        let quadratic =
+            U256::from(p.alpha_abs) * U256::from(p.decimals.beta) * (s_b * s_b - s_a * s_a);
+        let denom = U256::from(2) * U256::from(p.decimals.alpha) * U256::from(p.decimals.beta) * d * d;
+
+        ((linear - quadratic) / denom).as_u128()
+    }
+
+    /// f64 reference of the curve, the price in meme per quote token is
+    /// `beta / d - alpha * s / d^2` at quote reserve `s`
+    struct Curve {
+        alpha: f64,
+        beta: f64,
+    }
+
+    impl Curve {
+        fn of(pool: &BoundPool) -> Self {
+            let p = &pool.config;
+
+            Self {
+                alpha: p.alpha_abs as f64 / p.decimals.alpha as f64,
+                beta: p.beta as f64 / p.decimals.beta as f64,
+            }
+        }
+
+        fn price(&self, s: u64) -> Result<f64> {
+            let d = DECIMALS_S as f64;
+
+            self.beta
+                .try_div(d)?
+                .try_sub(self.alpha.try_mul(s as f64)?.try_div(d.try_mul(d)?)?)
+        }
+
+        /// Meme tokens released while the quote reserve moves from `s_a` to `s_b`
+        fn delta_m(&self, s_a: u64, s_b: u64) -> Result<f64> {
+            let d = DECIMALS_S as f64;
+            let (s_a, s_b) = (s_a as f64, s_b as f64);
+
+            let linear = self.beta.try_mul(s_b.try_sub(s_a)?)?.try_div(d)?;
+            let quadratic = self
+                .alpha
+                .try_mul(s_b.try_sub(s_a)?.try_mul(s_b.try_add(s_a)?)?)?
+                .try_div(2f64.try_mul(d)?.try_mul(d)?)?;
+
+            linear.try_sub(quadratic)
+        }
+
+        /// Quote tokens released when selling `delta_m` meme tokens at quote
+        /// reserve `s_b`, the positive root of `alpha / (2 d^2) x^2 + p(s_b) x = delta_m`
+        fn delta_s(&self, s_b: u64, delta_m: u64) -> Result<f64> {
+            let d = DECIMALS_S as f64;
+            let p_b = self.price(s_b)?;
+            let delta_m = delta_m as f64;
+
+            let root = p_b
+                .try_mul(p_b)?
+                .try_add(2f64.try_mul(self.alpha)?.try_mul(delta_m)?.try_div(d.try_mul(d)?)?)?
+                .try_sqrt()?;
+
+            2f64.try_mul(delta_m)?.try_div(p_b.try_add(root)?)
+        }
+    }
+
+    fn gamma_s() -> impl Strategy<Value = u64> {
+        10 * SOL..1_000 * SOL
+    }
+
+    fn quote_amount() -> impl Strategy<Value = u64> {
+        prop_oneof![1u64..1_000, 1_000u64..SOL, SOL..1_000 * SOL]
+    }
+
+    /// Quote reserves `s_a < s_b <= gamma_s` of the curve
+    fn reserves(gamma_s: u64, start_bps: u64, delta: u64) -> (u64, u64) {
+        let s_a = gamma_s * start_bps / 10_000;
+
+        (s_a, min(s_a + delta, gamma_s))
+    }
+
+    #[test]
+    fn test_check_deadline() {
+        assert!(check_deadline(None, i64::MAX).is_ok());
+        assert!(check_deadline(Some(100), 99).is_ok());
+        assert!(check_deadline(Some(100), 100).is_ok());
+        assert!(check_deadline(Some(100), 101).is_err());
+    }
+
+    #[test]
+    fn test_swap_amounts_below_min_output() {
+        let pool = new_pool(100 * SOL);
+        let buy = pool.swap_amounts(SOL, 0, true).unwrap();
+
+        assert_eq!(
+            pool.swap_amounts(SOL, buy.amount_out + 1, true).unwrap_err(),
+            error!(AmmError::SlippageExceeded)
+        );
+
+        let pool = pool_at(100 * SOL, 50 * SOL);
+        let sell = pool.swap_amounts(buy.amount_out, 0, false).unwrap();
+
+        assert_eq!(
+            pool.swap_amounts(buy.amount_out, sell.amount_out + 1, false).unwrap_err(),
+            error!(AmmError::SlippageExceeded)
+        );
+    }
+
+    #[test]
+    fn test_infeasible_curve_params() {
+        let gamma_s = 100 * SOL as u128;
+
+        let curve = |gamma_m: u128, omega_m: u128, num: u64, denom: u64| {
+            let alpha = compute_alpha_abs(gamma_s, DECIMALS_S, gamma_m, omega_m, num, denom);
+            let beta = compute_beta(gamma_s, DECIMALS_S, gamma_m, omega_m, num, denom, 1_000_000);
+
+            (alpha.unwrap_err(), beta.unwrap_err())
+        };
+
+        let invalid_arg = (error!(AmmError::InvalidArg), error!(AmmError::InvalidArg));
+        assert_eq!(curve(0, DEFAULT_MAX_M_LP, 1, 1), invalid_arg);
+        assert_eq!(curve(DEFAULT_MAX_M, 0, 1, 1), invalid_arg);
+        assert_eq!(curve(DEFAULT_MAX_M, DEFAULT_MAX_M_LP, 1, 0), invalid_arg);
+
+        // The guards behind the split check don't divide by a zero denominator either
+        assert_eq!(
+            check_slope(DEFAULT_MAX_M, DEFAULT_MAX_M_LP, 1, 0).unwrap_err(),
+            error!(AmmError::DivideByZero)
+        );
+        assert_eq!(
+            check_intercept(DEFAULT_MAX_M, DEFAULT_MAX_M_LP, 1, 0).unwrap_err(),
+            error!(AmmError::DivideByZero)
+        );
+
+        // A factored LP share of twice the trading supply leaves a zero starting
+        // price, anything above it a negative one
+        for (omega_m, num) in [(DEFAULT_MAX_M, 2), (DEFAULT_MAX_M_LP, 5)] {
+            assert_eq!(
+                curve(DEFAULT_MAX_M, omega_m, num, 1),
+                (
+                    error!(AmmError::BondingCurveMustBeNegativelySloped),
+                    error!(AmmError::BondingCurveInterceptMustBePositive)
+                )
+            );
+        }
+
+        // A factored LP share equal to the trading supply makes the curve flat
+        assert_eq!(
+            compute_alpha_abs(gamma_s, DECIMALS_S, DEFAULT_MAX_M, DEFAULT_MAX_M, 1, 1).unwrap_err(),
+            error!(AmmError::BondingCurveMustBeNegativelySloped)
+        );
+
+        // A price factor too large to scale the LP share by
+        assert_eq!(
+            curve(DEFAULT_MAX_M, u128::MAX, 2, 1),
+            (error!(AmmError::MathOverflow), error!(AmmError::MathOverflow))
+        );
+    }
+
+    #[test]
+    fn test_meme_vault_surplus_tracks_tickets() {
+        let mut pool = new_pool(100 * SOL);
+        let fees = pool.fees;
+        let minted = pool.config.gamma_m + pool.config.omega_m;
+        assert_eq!(pool.meme_vault_surplus(minted).unwrap(), 0);
+
+        // Bought tokens stay in the vault as tickets
+        let buy = pool.execute_buy(SOL, 0, &fees, &PriceLimit::default(), 0).unwrap();
+        pool.hold_ticket_tokens(buy.amount_out).unwrap();
+        assert_eq!(pool.meme_vault_surplus(minted).unwrap(), 0);
+
+        let sell = pool.swap_amounts(buy.amount_out / 2, 0, false).unwrap();
+        pool.apply_swap(&sell, false).unwrap();
+        pool.release_ticket_tokens(sell.amount_in + sell.admin_fee_in);
+        assert_eq!(pool.meme_vault_surplus(minted).unwrap(), 0);
+
+        // Tokens sent straight to the vault are surplus, missing ones a deficit
+        assert_eq!(pool.meme_vault_surplus(minted + 5).unwrap(), 5);
+        assert!(pool.meme_vault_surplus(minted - 1).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn delta_m_strategies_agree(
+            gamma_s in gamma_s(),
+            start_bps in 0u64..10_000,
+            delta in quote_amount(),
+        ) {
+            let pool = new_pool(gamma_s);
+            let (s_a, s_b) = reserves(gamma_s, start_bps, delta);
+            prop_assume!(s_a < s_b);
+
+            let p = &pool.config;
+            let m1 = delta_m1_strategy(
+                p.alpha_abs, p.beta, p.decimals.alpha, p.decimals.beta, s_a as u128, s_b as u128,
+            );
+            let m2 = delta_m2_strategy(
+                p.alpha_abs, p.beta, p.decimals.alpha, p.decimals.beta, s_a as u128, s_b as u128,
+            );
+
+            if let (Some(m1), Some(m2)) = (m1, m2) {
+                prop_assert!(m1 <= m2 && m1 + 1 >= m2, "m1: {}, m2: {}", m1, m2);
+            }
+        }
+
+        #[test]
+        fn delta_m_rounding_favors_the_pool(
+            gamma_s in gamma_s(),
+            start_bps in 0u64..10_000,
+            delta in quote_amount(),
+        ) {
+            let pool = new_pool(gamma_s);
+            let (s_a, s_b) = reserves(gamma_s, start_bps, delta);
+            prop_assume!(s_a < s_b);
+
+            let delta_m = pool.compute_delta_m(s_a, s_b).unwrap() as u128;
+            let exact = exact_delta_m(&pool, s_a, s_b);
+
+            prop_assert!(delta_m <= exact, "delta_m: {}, exact: {}", delta_m, exact);
+            prop_assert!(delta_m + 1 >= exact, "delta_m: {}, exact: {}", delta_m, exact);
+        }
+
+        #[test]
+        fn delta_m_matches_reference(
+            gamma_s in gamma_s(),
+            start_bps in 0u64..10_000,
+            delta in quote_amount(),
+        ) {
+            let pool = new_pool(gamma_s);
+            let (s_a, s_b) = reserves(gamma_s, start_bps, delta);
+            prop_assume!(s_a < s_b);
+
+            let delta_m = pool.compute_delta_m(s_a, s_b).unwrap() as f64;
+            let reference = Curve::of(&pool).delta_m(s_a, s_b).unwrap();
+
+            prop_assert!(
+                (delta_m - reference).abs() <= 2.0 + reference * 1e-9,
+                "delta_m: {}, reference: {}",
+                delta_m,
+                reference
+            );
+        }
+
+        #[test]
+        fn delta_s_matches_reference(
+            gamma_s in gamma_s(),
+            start_bps in 1u64..=10_000,
+            delta_m_bps in 1u64..=10_000,
+        ) {
+            let s_b = gamma_s * start_bps / 10_000;
+            let pool = new_pool(gamma_s);
+
+            let sold = pool.compute_delta_m(0, s_b).unwrap();
+            let delta_m = sold * delta_m_bps / 10_000;
+            prop_assume!(delta_m > 0);
+
+            let delta_s = pool.compute_delta_s(s_b, delta_m).unwrap() as f64;
+            let reference = Curve::of(&pool).delta_s(s_b, delta_m).unwrap();
+
+            prop_assert!(
+                (delta_s - reference).abs() <= 2.0 + reference * 1e-9,
+                "delta_s: {}, reference: {}",
+                delta_s,
+                reference
+            );
+        }
+
+        #[test]
+        fn delta_s_inverts_delta_m(
+            gamma_s in gamma_s(),
+            start_bps in 0u64..10_000,
+            delta in quote_amount(),
+        ) {
+            let pool = new_pool(gamma_s);
+            let (s_a, s_b) = reserves(gamma_s, start_bps, delta);
+            prop_assume!(s_a < s_b);
+
+            let delta_m = pool.compute_delta_m(s_a, s_b).unwrap();
+            prop_assume!(delta_m > 0);
+
+            let delta_s = pool.compute_delta_s(s_b, delta_m).unwrap();
+
+            prop_assert!(
+                delta_s <= s_b - s_a && delta_s + DELTA_S_ROUNDING >= s_b - s_a,
+                "delta_s: {}, s_b - s_a: {}",
+                delta_s,
+                s_b - s_a
+            );
+        }
+
+        #[test]
+        fn buy_then_sell_never_profits(
+            gamma_s in gamma_s(),
+            start_bps in 0u64..10_000,
+            quote_in in quote_amount(),
+        ) {
+            let mut pool = pool_at(gamma_s, gamma_s * start_bps / 10_000);
+            let fees = pool.fees;
+
+            let buy = pool.execute_buy(quote_in, 0, &fees, &PriceLimit::default(), 0).unwrap();
+            prop_assume!(!pool.locked && buy.amount_out > 0);
+
+            let sell = pool.swap_amounts(buy.amount_out, 0, false).unwrap();
+
+            prop_assert!(
+                sell.amount_out <= buy.amount_in + buy.admin_fee_in,
+                "paid: {}, received: {}",
+                buy.amount_in + buy.admin_fee_in,
+                sell.amount_out
+            );
+        }
+
+        #[test]
+        fn delta_s_rounds_down(
+            gamma_s in gamma_s(),
+            start_bps in 1u64..=10_000,
+            delta_m_bps in 1u64..=10_000,
+        ) {
+            let s_b = gamma_s * start_bps / 10_000;
+            let pool = new_pool(gamma_s);
+
+            let sold = pool.compute_delta_m(0, s_b).unwrap();
+            let delta_m = sold * delta_m_bps / 10_000;
+            prop_assume!(delta_m > 0);
+
+            let delta_s = pool.compute_delta_s(s_b, delta_m).unwrap();
+
+            // The curve segment paid out is never worth more than what was sold
+            prop_assert!(
+                exact_delta_m(&pool, s_b - delta_s, s_b) <= delta_m as u128,
+                "delta_s: {}, delta_m: {}",
+                delta_s,
+                delta_m
+            );
+        }
+
+        #[test]
+        fn same_slot_sell_pays_penalty(
+            gamma_s in gamma_s(),
+            quote_in in SOL..100 * SOL,
+        ) {
+            let mut pool = new_pool(gamma_s);
+            let fees = pool.fees;
+
+            let buy = pool.execute_buy(quote_in, 0, &fees, &PriceLimit::default(), 7).unwrap();
+            prop_assume!(!pool.locked);
+
+            let same_slot = pool.sell_fees(&fees, 7).unwrap();
+            let next_slot = pool.sell_fees(&fees, 8).unwrap();
+            prop_assert_eq!(next_slot, fees);
+
+            let penalized = pool
+                .swap_amounts_with_fees(buy.amount_out, 0, false, &same_slot)
+                .unwrap();
+            let regular = pool
+                .swap_amounts_with_fees(buy.amount_out, 0, false, &next_slot)
+                .unwrap();
+
+            prop_assert!(penalized.amount_out < regular.amount_out);
+            prop_assert!(penalized.admin_fee_out > regular.admin_fee_out);
+        }
+
+        #[test]
+        fn spot_price_matches_reference(
+            gamma_s in gamma_s(),
+            start_bps in 0u64..10_000,
+        ) {
+            let pool = pool_at(gamma_s, gamma_s * start_bps / 10_000);
+
+            let spot_price = pool.spot_price().unwrap() as f64 / PRICE_PRECISION as f64;
+            let reference = MEME_TOKEN_DECIMALS as f64
+                / Curve::of(&pool).price(pool.quote_reserve.tokens).unwrap();
+
+            prop_assert!(
+                (spot_price - reference).abs() <= reference * 1e-9 + 1.0 / PRICE_PRECISION as f64,
+                "spot_price: {}, reference: {}",
+                spot_price,
+                reference
+            );
+            let progress_bps = pool.progress_bps().unwrap();
+            prop_assert!(progress_bps <= start_bps && progress_bps + 1 >= start_bps);
+        }
+
+        #[test]
+        fn limited_swaps_fill_within_limit(
+            gamma_s in gamma_s(),
+            start_bps in 0u64..9_000,
+            quote_in in SOL..100 * SOL,
+            max_price_impact_bps in 1u64..1_000,
+        ) {
+            let pool = pool_at(gamma_s, gamma_s * start_bps / 10_000);
+            let fees = pool.fees;
+
+            let full = pool.swap_amounts(quote_in, 0, true).unwrap();
+            let limit = PriceLimit {
+                avg_price: None,
+                max_price_impact_bps: Some(max_price_impact_bps),
+            };
+
+            let filled = pool.limited_swap_amounts(quote_in, 0, true, &fees, &limit);
+            // Even the smallest fill may move the price further than the limit
+            prop_assume!(
+                !matches!(&filled, Err(err) if *err == error!(AmmError::PriceLimitExceeded))
+            );
+            let filled = filled.unwrap();
+
+            prop_assert!(filled.amount_in <= full.amount_in);
+            prop_assert!(filled.amount_out <= full.amount_out);
+            prop_assert!(pool.within_price_limit(&filled, true, &limit).unwrap());
+
+            let mut pool = pool.clone();
+            pool.apply_swap(&filled, true).unwrap();
+
+            let sell = pool.swap_amounts(filled.amount_out, 0, false).unwrap();
+            prop_assume!(sell.amount_out > 0);
+
+            let limit = PriceLimit {
+                avg_price: Some(BoundPool::avg_price(sell.amount_out, filled.amount_out).unwrap() + 1),
+                max_price_impact_bps: None,
+            };
+            let sold = pool.limited_swap_amounts(filled.amount_out, 0, false, &fees, &limit);
+
+            // Smaller sells get a better average price, a limit above the one
+            // of the whole amount can only be met by a partial fill
+            match sold {
+                Ok(sold) => prop_assert!(sold.amount_in < sell.amount_in),
+                Err(err) => prop_assert_eq!(err, error!(AmmError::PriceLimitExceeded)),
+            }
+        }
+
+        #[test]
+        fn graduating_buy_pays_only_for_the_remaining_curve(
+            gamma_s in gamma_s(),
+            start_bps in 0u64..10_000,
+            excess_bps in 0u64..10_000,
+        ) {
+            let s_a = gamma_s * start_bps / 10_000;
+            let mut pool = pool_at(gamma_s, s_a);
+            let fees = pool.fees;
+            let meme_reserve = pool.meme_reserve.tokens;
+
+            let remaining = gamma_s - s_a;
+            prop_assume!(remaining > 0);
+
+            let used = fees.get_gross_quote_amount(remaining).unwrap();
+            let quote_in = used + used * excess_bps / 10_000;
+
+            let buy = pool
+                .execute_buy(quote_in, 0, &fees, &PriceLimit::default(), 0)
+                .unwrap();
+
+            prop_assert_eq!(buy.amount_in, remaining);
+            prop_assert_eq!(buy.amount_in + buy.admin_fee_in, used);
+            prop_assert!(buy.admin_fee_in <= fees.get_fee_quote_amount(quote_in).unwrap());
+            prop_assert_eq!(buy.amount_out + buy.admin_fee_out, meme_reserve);
+            prop_assert_eq!(pool.meme_reserve.tokens, 0);
+            prop_assert_eq!(pool.quote_reserve.tokens, gamma_s);
+            prop_assert!(pool.locked);
+        }
+
+        #[test]
+        fn compute_a_rounds_down(
+            u in any::<u128>(),
+            v in any::<u64>(),
+            w in any::<u128>(),
+            alpha_decimals_exp in 0u32..9,
+        ) {
+            let (u, v, w) = (U256::from(u), U256::from(v), U256::from(w));
+            let alpha_decimals = U256::from(10u64.pow(alpha_decimals_exp));
+
+            if let Some(a) = compute_a(u, alpha_decimals, w, v, U256::from(1)) {
+                let exact = (u * u)
+                    .checked_mul(alpha_decimals)
+                    .checked_add_((v * v).checked_mul(w));
+
+                if let (Some(exact), Some(a_squared)) = (exact, a.checked_mul(a)) {
+                    prop_assert!(a_squared <= exact);
+                }
+            }
+        }
+
+        #[test]
+        fn multiply_divide_is_exact_without_overflow(
+            numerators in proptest::collection::vec(any::<u64>(), 1..4),
+            denominators in proptest::collection::vec(1u64.., 1..3),
+        ) {
+            let numerator: U256 = numerators.iter().fold(U256::from(1), |acc, n| acc * U256::from(*n));
+            let denominator: U256 = denominators.iter().fold(U256::from(1), |acc, d| acc * U256::from(*d));
+
+            let result = multiply_divide(
+                numerators.into_iter().map(U256::from).collect(),
+                denominators.into_iter().map(U256::from).collect(),
+            );
+
+            prop_assert_eq!(result, Some(numerator / denominator));
+        }
+
+        #[test]
+        fn multiply_divide_rounds_down_on_overflow(
+            numerators in proptest::collection::vec(any::<u128>(), 3..5),
+            denominators in proptest::collection::vec(1u128.., 1..4),
+        ) {
+            let reference = numerators.iter().map(|n| *n as f64).product::<f64>()
+                / denominators.iter().map(|d| *d as f64).product::<f64>();
+
+            let result = multiply_divide(
+                numerators.into_iter().map(U256::from).collect(),
+                denominators.into_iter().map(U256::from).collect(),
+            );
+
+            if let Some(result) = result {
+                let result = result.to_string().parse::<f64>().unwrap();
+                prop_assert!(result <= reference * (1.0 + 1e-9) + 1.0);
+            }
+        }
+    }
+}
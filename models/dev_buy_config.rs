@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+/// Admin controlled cap on the dev-buy of new pools of a quote mint.
+///
+/// Quote mints without a config don't allow dev-buys.
+#[account]
+#[derive(Default)]
+pub struct DevBuyConfig {
+    pub quote_mint: Pubkey,
+    /// Share of the trading tokens a creator can dev-buy, in basis points
+    pub max_bps: u64,
+    pub padding: [u8; 32],
+}
+
+impl DevBuyConfig {
+    pub const DEV_BUY_PREFIX: &'static [u8; 7] = b"dev_buy";
+
+    pub fn space() -> usize {
+        let discriminant = 8;
+        let quote_mint = 32;
+        let max_bps = 8;
+        let padding = 32;
+
+        discriminant + quote_mint + max_bps + padding
+    }
+
+    /// The dev-buy cap of a pool, zero without a config.
+    pub fn max_bps(config: Option<&Self>) -> u64 {
+        config.map_or(0, |config| config.max_bps)
+    }
+}
@@ -0,0 +1,193 @@
+use crate::MonopolyError;
+use anchor_lang::prelude::*;
+use solana_program::hash::{hash, hashv};
+
+/// Slots after the commitment within which a roll has to be revealed.
+///
+/// Has to stay well below the 512 slots `SlotHashes` keeps, so that the hash
+/// the roll is drawn from is still available at reveal.
+pub const REVEAL_WINDOW_SLOTS: u64 = 150;
+
+/// Outcome of rolling two six-sided dice.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct DiceRoll {
+    pub first: u8,
+    pub second: u8,
+    /// Randomness the roll was derived from, can be used for follow-up draws
+    pub seed: [u8; 32],
+}
+
+impl DiceRoll {
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        Self {
+            first: die_from_bytes(&seed[0..8]),
+            second: die_from_bytes(&seed[8..16]),
+            seed,
+        }
+    }
+
+    pub fn total(&self) -> u8 {
+        self.first + self.second
+    }
+
+    pub fn is_double(&self) -> bool {
+        self.first == self.second
+    }
+}
+
+fn die_from_bytes(bytes: &[u8]) -> u8 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(bytes);
+
+    (u64::from_le_bytes(buf) % 6) as u8 + 1
+}
+
+/// Source of randomness for dice rolls.
+///
+/// The game only depends on this trait, so an oracle backed source (e.g. one
+/// reading a VRF result account) can replace the default commit-reveal scheme
+/// without touching the turn logic.
+pub trait DiceSource {
+    fn roll(&self) -> Result<DiceRoll>;
+}
+
+/// Commit-reveal randomness that works without any oracle.
+///
+/// The player commits to `hash(secret)` in one slot and reveals the secret
+/// within [`REVEAL_WINDOW_SLOTS`]. The secret is mixed with the hash of the
+/// first slot after the commitment, which nobody knows when committing and
+/// which doesn't change with the slot of the reveal, so the player can neither
+/// pick a secret nor a reveal slot that produces a chosen roll.
+pub struct CommitReveal<'a> {
+    pub commitment: &'a [u8; 32],
+    pub secret: &'a [u8; 32],
+    /// Hash of the first slot after `commit_slot`, see [`slot_hash_after`]
+    pub slot_hash: &'a [u8; 32],
+    pub player: Pubkey,
+    pub commit_slot: u64,
+    pub reveal_slot: u64,
+}
+
+impl<'a> CommitReveal<'a> {
+    pub fn commitment_of(secret: &[u8; 32]) -> [u8; 32] {
+        hash(secret).to_bytes()
+    }
+
+    /// Whether a roll committed in `commit_slot` can no longer be revealed in
+    /// `slot`.
+    pub fn is_expired(commit_slot: u64, slot: u64) -> bool {
+        slot > commit_slot.saturating_add(REVEAL_WINDOW_SLOTS)
+    }
+}
+
+impl<'a> DiceSource for CommitReveal<'a> {
+    fn roll(&self) -> Result<DiceRoll> {
+        if Self::commitment_of(self.secret) != *self.commitment {
+            return Err(error!(MonopolyError::InvalidReveal));
+        }
+
+        if self.reveal_slot <= self.commit_slot {
+            return Err(error!(MonopolyError::RevealTooEarly));
+        }
+
+        if Self::is_expired(self.commit_slot, self.reveal_slot) {
+            return Err(error!(MonopolyError::RevealExpired));
+        }
+
+        let seed = hashv(&[
+            self.secret,
+            self.slot_hash,
+            self.player.as_ref(),
+            &self.commit_slot.to_le_bytes(),
+        ]);
+
+        Ok(DiceRoll::from_seed(seed.to_bytes()))
+    }
+}
+
+/// Hash of the first slot after `slot` with a block, read from the raw data
+/// of the `SlotHashes` sysvar, whose entries are sorted newest first.
+///
+/// Returns `None` if no later slot was hashed yet, fails if `slot` already
+/// dropped out of the sysvar.
+pub fn slot_hash_after(slot_hashes: &[u8], slot: u64) -> Result<Option<[u8; 32]>> {
+    const ENTRY_SIZE: usize = 8 + 32;
+
+    let len = slot_hashes
+        .get(..8)
+        .map(|len| u64::from_le_bytes(len.try_into().unwrap()) as usize)
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    let mut slot_hash = None;
+
+    for i in 0..len {
+        let entry = slot_hashes
+            .get(8 + i * ENTRY_SIZE..8 + (i + 1) * ENTRY_SIZE)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let entry_slot = u64::from_le_bytes(entry[..8].try_into().unwrap());
+
+        if entry_slot <= slot {
+            return Ok(slot_hash);
+        }
+
+        slot_hash = Some(entry[8..].try_into().unwrap());
+    }
+
+    // Every remaining entry is newer than `slot`, so the first slot after it
+    // may have dropped out already
+    if slot_hash.is_some() {
+        return Err(error!(MonopolyError::RevealExpired));
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn slot_hashes(slots: &[u64]) -> Vec<u8> {
+        let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+
+        for slot in slots {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&[*slot as u8; 32]);
+        }
+
+        data
+    }
+
+    #[test]
+    fn test_slot_hash_after_picks_first_later_slot() {
+        // Slot 12 was skipped
+        let data = slot_hashes(&[14, 13, 11, 10]);
+
+        assert_eq!(slot_hash_after(&data, 10).unwrap(), Some([11; 32]));
+        assert_eq!(slot_hash_after(&data, 11).unwrap(), Some([13; 32]));
+        assert_eq!(slot_hash_after(&data, 14).unwrap(), None);
+        assert!(slot_hash_after(&data, 9).is_err());
+    }
+
+    #[test]
+    fn test_roll_ignores_reveal_slot() {
+        let secret = [7; 32];
+        let commitment = CommitReveal::commitment_of(&secret);
+        let slot_hash = [3; 32];
+
+        let reveal = |reveal_slot| {
+            CommitReveal {
+                commitment: &commitment,
+                secret: &secret,
+                slot_hash: &slot_hash,
+                player: Pubkey::default(),
+                commit_slot: 100,
+                reveal_slot,
+            }
+            .roll()
+        };
+
+        assert_eq!(reveal(102).unwrap(), reveal(100 + REVEAL_WINDOW_SLOTS).unwrap());
+        assert!(reveal(100).is_err());
+        assert!(reveal(101 + REVEAL_WINDOW_SLOTS).is_err());
+    }
+}
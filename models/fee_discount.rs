@@ -0,0 +1,221 @@
+use crate::err::AmmError;
+use crate::libraries::MulDiv;
+use crate::models::fees::Fees;
+use anchor_lang::prelude::*;
+use std::mem;
+
+pub const MAX_DISCOUNT_TIERS: usize = 4;
+pub const DISCOUNT_PRECISION: u64 = 100_000;
+
+/// A single step of the points-burn discount schedule.
+///
+/// Burning at least `points_amount` points grants a `discount` share (out of
+/// [`DISCOUNT_PRECISION`]) off the quote fee of the swap.
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub struct FeeDiscountTier {
+    pub points_amount: u64,
+    pub discount: u64,
+}
+
+/// Admin controlled schedule of fee discounts available for burning points.
+#[account]
+#[derive(Default)]
+pub struct FeeDiscountConfig {
+    pub tiers: [FeeDiscountTier; MAX_DISCOUNT_TIERS],
+    pub padding: [u8; 32],
+}
+
+impl FeeDiscountConfig {
+    pub const FEE_DISCOUNT_PREFIX: &'static [u8; 12] = b"fee_discount";
+
+    pub fn space() -> usize {
+        let discriminant = 8;
+        let tiers = mem::size_of::<FeeDiscountTier>() * MAX_DISCOUNT_TIERS;
+        let padding = 32;
+
+        discriminant + tiers + padding
+    }
+
+    /// Returns the best tier reachable by burning at most `max_points_to_burn`
+    /// points. Tiers with zero discount are treated as unset.
+    pub fn best_tier(&self, max_points_to_burn: u64) -> Option<FeeDiscountTier> {
+        self.tiers
+            .iter()
+            .filter(|tier| tier.discount > 0 && tier.points_amount <= max_points_to_burn)
+            .max_by_key(|tier| tier.discount)
+            .copied()
+    }
+
+    /// Tiers must require strictly more points for strictly higher discounts,
+    /// and no discount may exceed the full fee.
+    pub fn validate(&self) -> bool {
+        let mut prev: Option<&FeeDiscountTier> = None;
+
+        for tier in self.tiers.iter().filter(|tier| tier.discount > 0) {
+            if tier.discount > DISCOUNT_PRECISION {
+                return false;
+            }
+
+            if let Some(prev) = prev {
+                if tier.points_amount <= prev.points_amount || tier.discount <= prev.discount {
+                    return false;
+                }
+            }
+
+            prev = Some(tier);
+        }
+
+        true
+    }
+}
+
+impl FeeDiscountTier {
+    /// Applies the discount to the quote side of the given fees.
+    pub fn apply(&self, fees: &Fees) -> Result<Fees> {
+        let discount = fees
+            .fee_quote_percent
+            .mul_div_floor(self.discount, DISCOUNT_PRECISION)
+            .ok_or(AmmError::MulDivOverflow)?;
+
+        Ok(Fees {
+            fee_meme_percent: fees.fee_meme_percent,
+            fee_quote_percent: fees
+                .fee_quote_percent
+                .checked_sub(discount)
+                .ok_or(AmmError::MathOverflow)?,
+        })
+    }
+}
+
+/// Resolves the fees to charge for a swap whose sender offered to burn up to
+/// `max_points_to_burn` points, together with the tier that was reached.
+pub fn discounted_fees(
+    config: Option<&FeeDiscountConfig>,
+    fees: &Fees,
+    max_points_to_burn: u64,
+) -> Result<(Fees, Option<FeeDiscountTier>)> {
+    if max_points_to_burn == 0 {
+        return Ok((*fees, None));
+    }
+
+    let config = match config {
+        Some(config) => config,
+        None => return Err(error!(AmmError::FeeDiscountUnavailable)),
+    };
+
+    match config.best_tier(max_points_to_burn) {
+        Some(tier) => Ok((tier.apply(fees)?, Some(tier))),
+        None => Ok((*fees, None)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::fees::{FEE, MEME_FEE};
+
+    fn tier(points_amount: u64, discount: u64) -> FeeDiscountTier {
+        FeeDiscountTier {
+            points_amount,
+            discount,
+        }
+    }
+
+    fn config(tiers: [FeeDiscountTier; MAX_DISCOUNT_TIERS]) -> FeeDiscountConfig {
+        FeeDiscountConfig {
+            tiers,
+            ..Default::default()
+        }
+    }
+
+    fn schedule() -> FeeDiscountConfig {
+        config([
+            tier(100, 10_000),
+            tier(1_000, 25_000),
+            tier(10_000, 50_000),
+            FeeDiscountTier::default(),
+        ])
+    }
+
+    fn fees() -> Fees {
+        Fees {
+            fee_meme_percent: MEME_FEE,
+            fee_quote_percent: FEE,
+        }
+    }
+
+    #[test]
+    fn test_best_tier_between_tiers() {
+        let config = schedule();
+
+        assert_eq!(config.best_tier(99), None);
+        assert_eq!(config.best_tier(100), Some(tier(100, 10_000)));
+        assert_eq!(config.best_tier(999), Some(tier(100, 10_000)));
+        assert_eq!(config.best_tier(5_000), Some(tier(1_000, 25_000)));
+        assert_eq!(config.best_tier(u64::MAX), Some(tier(10_000, 50_000)));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(schedule().validate());
+        assert!(config(Default::default()).validate());
+
+        // Tiers must grow in both points and discount
+        let unsorted = config([
+            tier(1_000, 25_000),
+            tier(100, 10_000),
+            FeeDiscountTier::default(),
+            FeeDiscountTier::default(),
+        ]);
+        assert!(!unsorted.validate());
+
+        let shrinking_discount = config([
+            tier(100, 25_000),
+            tier(1_000, 10_000),
+            FeeDiscountTier::default(),
+            FeeDiscountTier::default(),
+        ]);
+        assert!(!shrinking_discount.validate());
+
+        // No discount may exceed the full fee
+        let over_full_fee = config([
+            tier(100, DISCOUNT_PRECISION + 1),
+            FeeDiscountTier::default(),
+            FeeDiscountTier::default(),
+            FeeDiscountTier::default(),
+        ]);
+        assert!(!over_full_fee.validate());
+    }
+
+    #[test]
+    fn test_apply_scales_quote_fee() {
+        let fees = fees();
+
+        let quarter_off = tier(0, DISCOUNT_PRECISION / 4).apply(&fees).unwrap();
+        assert_eq!(quarter_off.fee_quote_percent, FEE * 3 / 4);
+        assert_eq!(quarter_off.fee_meme_percent, fees.fee_meme_percent);
+
+        let full = tier(0, DISCOUNT_PRECISION).apply(&fees).unwrap();
+        assert_eq!(full.fee_quote_percent, 0);
+
+        let none = tier(0, 0).apply(&fees).unwrap();
+        assert_eq!(none, fees);
+    }
+
+    #[test]
+    fn test_discounted_fees() {
+        let fees = fees();
+        let config = schedule();
+
+        assert_eq!(
+            discounted_fees(None, &fees, 100).unwrap_err(),
+            error!(AmmError::FeeDiscountUnavailable)
+        );
+        assert_eq!(discounted_fees(None, &fees, 0).unwrap(), (fees, None));
+        assert_eq!(discounted_fees(Some(&config), &fees, 99).unwrap(), (fees, None));
+
+        let (discounted, reached) = discounted_fees(Some(&config), &fees, 1_000).unwrap();
+        assert_eq!(reached, Some(tier(1_000, 25_000)));
+        assert_eq!(discounted.fee_quote_percent, FEE * 3 / 4);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::err::AmmError;
 use crate::libraries::MulDiv;
 use anchor_lang::prelude::*;
 
@@ -5,6 +6,7 @@ pub const MEME_FEE: u64 = 0; // 0%
 pub const FEE: u64 = 10_000_000; // 1%
 pub const LAUNCH_FEE: u64 = 50_000_000; // 5%
 pub const COMMS_FEE: u64 = 50_000_000; // 5%
+pub const SAME_SLOT_SELL_FEE: u64 = 20_000_000; // 2%
 pub const FEE_PRECISION: u64 = 1_000_000_000;
 
 #[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default)]
@@ -21,8 +23,43 @@ impl Fees {
     pub fn get_fee_quote_amount(&self, amount: u64) -> Result<u64> {
         get_fee_amount(amount, self.fee_quote_percent)
     }
+
+    /// Smallest quote amount that leaves at least `net_amount` after its
+    /// quote fee
+    pub fn get_gross_quote_amount(&self, net_amount: u64) -> Result<u64> {
+        let net_percent = FEE_PRECISION
+            .checked_sub(self.fee_quote_percent)
+            .ok_or(AmmError::MathOverflow)?;
+
+        Ok(net_amount
+            .mul_div_ceil(FEE_PRECISION, net_percent)
+            .ok_or(AmmError::MulDivOverflow)?)
+    }
 }
 
 pub fn get_fee_amount(x: u64, percent: u64) -> Result<u64> {
-    Ok(x.mul_div_ceil(percent, FEE_PRECISION).unwrap())
+    Ok(x
+        .mul_div_ceil(percent, FEE_PRECISION)
+        .ok_or(AmmError::MulDivOverflow)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_fee_amount_overflow() {
+        assert_eq!(
+            get_fee_amount(u64::MAX, 2 * FEE_PRECISION).unwrap_err(),
+            error!(AmmError::MulDivOverflow)
+        );
+        assert_eq!(get_fee_amount(u64::MAX, FEE_PRECISION).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_get_fee_amount_rounds_up() {
+        assert_eq!(get_fee_amount(1_000, FEE).unwrap(), 10);
+        assert_eq!(get_fee_amount(1, FEE).unwrap(), 1);
+        assert_eq!(get_fee_amount(0, FEE).unwrap(), 0);
+    }
 }
@@ -1,5 +1,10 @@
+pub mod airdrop;
+pub mod board;
 pub mod bound;
 pub mod chan_swap;
+pub mod dev_buy_config;
+pub mod dice;
+pub mod fee_discount;
 pub mod fee_distribution;
 pub mod fees;
 pub mod points_epoch;
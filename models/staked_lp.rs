@@ -1,4 +1,5 @@
 use crate::consts::LOCK_TIME;
+use crate::err::AmmError;
 use crate::vesting::VestingData;
 use anchor_lang::prelude::*;
 use std::mem;
@@ -14,10 +15,14 @@ pub struct MemeTicket {
     pub withdraws_chan: u64,
     pub until_timestamp: i64,
     pub vesting: VestingData,
+    /// Holds airdropped tokens, which can't be sold back into the curve and
+    /// are only paid out through vesting
+    pub airdrop: bool,
 }
 
 impl MemeTicket {
     pub const ADMIN_TICKET_PREFIX: &'static [u8; 12] = b"admin_ticket";
+    pub const CREATOR_TICKET_PREFIX: &'static [u8; 14] = b"creator_ticket";
 
     pub fn space() -> usize {
         let discriminant = 8;
@@ -29,7 +34,8 @@ impl MemeTicket {
         let withdraws_chan = 8;
         let until_timestamp = 8;
         let vesting = mem::size_of::<VestingData>();
-        let padding = 64;
+        let airdrop = 1;
+        let padding = 63;
 
         discriminant
             + owner
@@ -40,24 +46,60 @@ impl MemeTicket {
             + withdraws_chan
             + until_timestamp
             + vesting
+            + airdrop
             + padding
     }
 
-    pub fn is_unlocked(&self) -> bool {
-        self.until_timestamp <= Clock::get().unwrap().unix_timestamp
+    pub fn is_unlocked(&self) -> Result<bool> {
+        Ok(self.until_timestamp <= Clock::get()?.unix_timestamp)
     }
 
-    pub fn setup(&mut self, pool: Pubkey, owner: Pubkey, amount: u64) {
+    /// Takes `amount` tokens out of the ticket, e.g. when they're sold back
+    /// into the pool.
+    pub fn debit(&mut self, amount: u64) -> Result<()> {
+        self.amount = self
+            .amount
+            .checked_sub(amount)
+            .ok_or(AmmError::NotEnoughTicketTokens)?;
+        self.vesting.notional = self
+            .vesting
+            .notional
+            .checked_sub(amount)
+            .ok_or(AmmError::InvariantViolation)?;
+
+        Ok(())
+    }
+
+    pub fn setup(&mut self, pool: Pubkey, owner: Pubkey, amount: u64) -> Result<()> {
         self.pool = pool;
         self.owner = owner;
         self.amount = amount;
         self.withdraws_meme = 0;
         self.withdraws_quote = 0;
-        self.until_timestamp = Clock::get().unwrap().unix_timestamp + LOCK_TIME;
+        self.until_timestamp = Clock::get()?.unix_timestamp + LOCK_TIME;
         msg!(&self.until_timestamp.to_string());
         self.vesting = VestingData {
             notional: amount,
             released: 0,
         };
+        self.airdrop = false;
+
+        Ok(())
+    }
+
+    /// Sets up a ticket of airdropped tokens, locked for the pool's vesting
+    /// period instead of the usual lock time.
+    pub fn setup_airdrop(
+        &mut self,
+        pool: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+        vesting_period: i64,
+    ) -> Result<()> {
+        self.setup(pool, owner, amount)?;
+        self.until_timestamp = Clock::get()?.unix_timestamp + vesting_period;
+        self.airdrop = true;
+
+        Ok(())
     }
 }
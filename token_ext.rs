@@ -0,0 +1,214 @@
+use crate::err::AmmError;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType,
+        StateWithExtensions,
+    },
+    state::Mint,
+};
+
+/// Mint extensions pools can work with. Anything else, e.g. a permanent
+/// delegate that could pull tokens out of the vaults, or a transfer hook that
+/// could block them, is rejected at pool creation.
+const SUPPORTED_MINT_EXTENSIONS: [ExtensionType; 3] = [
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+];
+
+pub fn validate_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<Mint>::unpack(&data)?;
+
+    for extension in mint_state.get_extension_types()? {
+        if !SUPPORTED_MINT_EXTENSIONS.contains(&extension) {
+            msg!("[UnsupportedMintExtension] {:?} on {}", extension, mint.key());
+
+            return Err(error!(AmmError::UnsupportedMintExtension));
+        }
+    }
+
+    Ok(())
+}
+
+/// Fee withheld by the mint when transferring `amount` tokens, i.e. the
+/// recipient receives `amount - transfer_fee(amount)`.
+pub fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+
+    epoch_transfer_fee(&mint.try_borrow_data()?, Clock::get()?.epoch, amount)
+}
+
+/// Amount to send so that the recipient receives exactly `amount` tokens.
+pub fn amount_with_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(amount);
+    }
+
+    epoch_amount_with_transfer_fee(&mint.try_borrow_data()?, Clock::get()?.epoch, amount)
+}
+
+fn epoch_transfer_fee(mint_data: &[u8], epoch: u64, amount: u64) -> Result<u64> {
+    with_transfer_fee_config(mint_data, |config| {
+        config.calculate_epoch_fee(epoch, amount)
+    })
+}
+
+fn epoch_amount_with_transfer_fee(mint_data: &[u8], epoch: u64, amount: u64) -> Result<u64> {
+    let fee = with_transfer_fee_config(mint_data, |config| {
+        config.calculate_inverse_epoch_fee(epoch, amount)
+    })?;
+
+    Ok(amount.checked_add(fee).ok_or(AmmError::MathOverflow)?)
+}
+
+fn with_transfer_fee_config(
+    mint_data: &[u8],
+    compute: impl Fn(&TransferFeeConfig) -> Option<u64>,
+) -> Result<u64> {
+    let mint_state = StateWithExtensions::<Mint>::unpack(mint_data)?;
+
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => Ok(compute(config).ok_or(AmmError::MathOverflow)?),
+        Err(_) => Ok(0),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_spl::token_2022::spl_token_2022::extension::{
+        permanent_delegate::PermanentDelegate, transfer_fee::TransferFee,
+        BaseStateWithExtensionsMut, StateWithExtensionsMut,
+    };
+    use proptest::prelude::*;
+    use solana_program::program_pack::Pack;
+
+    /// Data of an initialized Token-2022 mint with the given extensions, each
+    /// set up by `init`.
+    fn mint_data(
+        extensions: &[ExtensionType],
+        init: impl FnOnce(&mut StateWithExtensionsMut<Mint>),
+    ) -> Vec<u8> {
+        let base = Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        };
+
+        if extensions.is_empty() {
+            let mut data = vec![0; Mint::LEN];
+            Mint::pack(base, &mut data).unwrap();
+
+            return data;
+        }
+
+        let len = ExtensionType::try_calculate_account_len::<Mint>(extensions).unwrap();
+        let mut data = vec![0; len];
+
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        init(&mut state);
+        state.base = base;
+        state.pack_base();
+        state.init_account_type().unwrap();
+
+        data
+    }
+
+    fn transfer_fee_mint(basis_points: u16, maximum_fee: u64) -> Vec<u8> {
+        mint_data(&[ExtensionType::TransferFeeConfig], |state| {
+            let fee = TransferFee {
+                epoch: 0u64.into(),
+                maximum_fee: maximum_fee.into(),
+                transfer_fee_basis_points: basis_points.into(),
+            };
+
+            let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+            config.older_transfer_fee = fee;
+            config.newer_transfer_fee = fee;
+        })
+    }
+
+    fn validate(data: &mut [u8]) -> Result<()> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mint = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            data,
+            &spl_token_2022::ID,
+            false,
+            0,
+        );
+
+        validate_mint_extensions(&mint)
+    }
+
+    #[test]
+    fn test_validate_mint_extensions() {
+        let mut plain = mint_data(&[], |_| {});
+        assert!(validate(&mut plain).is_ok());
+
+        let mut with_fee = transfer_fee_mint(100, 1_000);
+        assert!(validate(&mut with_fee).is_ok());
+
+        let mut with_delegate = mint_data(&[ExtensionType::PermanentDelegate], |state| {
+            let delegate = state.init_extension::<PermanentDelegate>(true).unwrap();
+            delegate.delegate = Some(Pubkey::new_unique()).try_into().unwrap();
+        });
+        assert_eq!(
+            validate(&mut with_delegate).unwrap_err(),
+            error!(AmmError::UnsupportedMintExtension)
+        );
+    }
+
+    #[test]
+    fn test_transfer_fee_without_config() {
+        let data = mint_data(&[], |_| {});
+
+        assert_eq!(epoch_transfer_fee(&data, 0, 1_000).unwrap(), 0);
+        assert_eq!(epoch_amount_with_transfer_fee(&data, 0, 1_000).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn test_transfer_fee_is_capped() {
+        let data = transfer_fee_mint(100, 5);
+
+        assert_eq!(epoch_transfer_fee(&data, 0, 100).unwrap(), 1);
+        assert_eq!(epoch_transfer_fee(&data, 0, 1_000_000).unwrap(), 5);
+        assert_eq!(epoch_amount_with_transfer_fee(&data, 0, 1_000_000).unwrap(), 1_000_005);
+    }
+
+    proptest! {
+        #[test]
+        fn amount_with_transfer_fee_delivers_amount(
+            basis_points in 0u16..=10_000,
+            maximum_fee in prop_oneof![0u64..1_000, 1_000u64..1 << 40, Just(u64::MAX)],
+            amount in 0u64..1 << 40,
+        ) {
+            let data = transfer_fee_mint(basis_points, maximum_fee);
+
+            let gross = match epoch_amount_with_transfer_fee(&data, 0, amount) {
+                Ok(gross) => gross,
+                // A 100% fee without a cap overflows the amount to send
+                Err(_) => {
+                    prop_assert!(basis_points == 10_000 && maximum_fee == u64::MAX);
+                    return Ok(());
+                }
+            };
+            let fee = epoch_transfer_fee(&data, 0, gross).unwrap();
+
+            prop_assert_eq!(gross - fee, amount);
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::consts::{DEFAULT_CLIFF, INSTANT_TOKEN_PERCENTAGE_DENOM, INSTANT_TOKEN_PERCENTAGE_NUM};
+use crate::err::AmmError;
 use crate::libraries::MulDiv;
 use anchor_lang::prelude::*;
 
@@ -15,45 +16,62 @@ pub struct VestingData {
     pub notional: u64,
 }
 
-pub fn default_config(vesting_time: i64) -> VestingConfig {
-    let current_ts = Clock::get().unwrap().unix_timestamp;
+pub fn default_config(vesting_time: i64) -> Result<VestingConfig> {
+    let current_ts = Clock::get()?.unix_timestamp;
 
-    VestingConfig {
+    Ok(VestingConfig {
         start_ts: current_ts,
         cliff_ts: current_ts + DEFAULT_CLIFF,
         end_ts: current_ts + DEFAULT_CLIFF + vesting_time,
-    }
+    })
 }
 
 impl VestingData {
-    pub fn total_vested(&self, config: &VestingConfig, current_ts: i64) -> u64 {
+    pub fn total_vested(&self, config: &VestingConfig, current_ts: i64) -> Result<u64> {
+        // A config without a vesting duration can't vest linearly
+        let total = config.duration() as u64;
+
+        if total == 0 {
+            return Err(error!(AmmError::DivideByZero));
+        }
+
         let cliff_amount = self
             .notional
             .mul_div_floor(INSTANT_TOKEN_PERCENTAGE_NUM, INSTANT_TOKEN_PERCENTAGE_DENOM)
-            .unwrap();
+            .ok_or(AmmError::MulDivOverflow)?;
         if current_ts <= config.cliff_ts {
-            return cliff_amount;
+            return Ok(cliff_amount);
         }
 
         if current_ts >= config.end_ts {
-            return self.notional;
+            return Ok(self.notional);
         }
 
         let passed = (current_ts as u64)
             .checked_sub(config.cliff_ts as u64)
-            .unwrap();
-        let total = config.duration() as u64;
+            .ok_or(AmmError::MathOverflow)?;
         let tokens_after_cliff = self.notional - cliff_amount;
 
-        cliff_amount + tokens_after_cliff.mul_div_floor(passed, total).unwrap()
+        Ok(cliff_amount
+            + tokens_after_cliff
+                .mul_div_floor(passed, total)
+                .ok_or(AmmError::MulDivOverflow)?)
     }
 
-    pub fn to_release(&self, config: &VestingConfig, current_ts: i64) -> u64 {
-        self.total_vested(config, current_ts) - self.released
+    pub fn to_release(&self, config: &VestingConfig, current_ts: i64) -> Result<u64> {
+        Ok(self
+            .total_vested(config, current_ts)?
+            .checked_sub(self.released)
+            .ok_or(AmmError::MathOverflow)?)
     }
 
-    pub fn release(&mut self, amount: u64) {
-        self.released += amount;
+    pub fn release(&mut self, amount: u64) -> Result<()> {
+        self.released = self
+            .released
+            .checked_add(amount)
+            .ok_or(AmmError::MathOverflow)?;
+
+        Ok(())
     }
 
     pub fn current_stake(&self) -> u64 {
@@ -66,3 +84,47 @@ impl VestingConfig {
         self.end_ts - self.cliff_ts
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_total_vested_zero_duration() {
+        let vesting = VestingData {
+            released: 0,
+            notional: 1_000,
+        };
+        let config = VestingConfig {
+            start_ts: 0,
+            cliff_ts: 100,
+            end_ts: 100,
+        };
+
+        assert_eq!(
+            vesting.total_vested(&config, 50).unwrap_err(),
+            error!(AmmError::DivideByZero)
+        );
+        assert_eq!(
+            vesting.total_vested(&config, 150).unwrap_err(),
+            error!(AmmError::DivideByZero)
+        );
+    }
+
+    #[test]
+    fn test_total_vested_linear() {
+        let vesting = VestingData {
+            released: 0,
+            notional: 1_000,
+        };
+        let config = VestingConfig {
+            start_ts: 0,
+            cliff_ts: 100,
+            end_ts: 200,
+        };
+
+        assert_eq!(vesting.total_vested(&config, 100).unwrap(), 100);
+        assert_eq!(vesting.total_vested(&config, 150).unwrap(), 550);
+        assert_eq!(vesting.total_vested(&config, 200).unwrap(), 1_000);
+    }
+}